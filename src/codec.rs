@@ -0,0 +1,53 @@
+// User-defined bytes encodings
+
+use std::{error::Error, fmt};
+
+/// A user-defined string encoding for bytes
+///
+/// Registered with [`Config::set_bytes_custom`](crate::Config::set_bytes_custom), the
+/// codec is used for byte values exactly like the built-in formats.
+///
+/// # Example
+///
+/// ```
+/// use std::sync::Arc;
+/// use serde_json_ext::{BytesCodec, Config};
+///
+/// struct Dotted;
+///
+/// impl BytesCodec for Dotted {
+///     fn encode(&self, value: &[u8]) -> String {
+///         value.iter().map(u8::to_string).collect::<Vec<_>>().join(".")
+///     }
+///
+///     fn decode(&self, value: &str) -> Result<Vec<u8>, Box<dyn std::error::Error + Send + Sync>> {
+///         Ok(value.split('.').map(str::parse).collect::<Result<_, _>>()?)
+///     }
+/// }
+///
+/// let config = Config::default().set_bytes_custom(Arc::new(Dotted));
+/// ```
+pub trait BytesCodec: Send + Sync {
+    /// Encodes bytes into a string
+    fn encode(&self, value: &[u8]) -> String;
+
+    /// Decodes a string into bytes
+    ///
+    /// The error is reported through the error of the deserializer.
+    fn decode(&self, value: &str) -> Result<Vec<u8>, Box<dyn Error + Send + Sync>>;
+}
+
+impl fmt::Debug for dyn BytesCodec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("BytesCodec")
+    }
+}
+
+/// Codecs are compared by identity, two handles are equal when they share the codec
+impl PartialEq for dyn BytesCodec {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::addr_eq(self, other)
+    }
+}
+
+impl Eq for dyn BytesCodec {}
@@ -1,5 +1,12 @@
+use std::{env, fmt, sync::Arc};
+
+use serde::{Deserialize, Serialize, de::Error as _};
+
+use crate::{BytesCodec, path::PathPattern};
+
 /// Bytes encoding format
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum BytesFormat {
     /// Default format (array of numbers)
     Default,
@@ -9,26 +16,493 @@ pub enum BytesFormat {
     Base64,
     /// Base64 URL-safe encoding
     Base64UrlSafe,
+    /// Base58 encoding
+    Base58,
+    /// Base58Check encoding (Base58 with a 4-byte double SHA-256 checksum)
+    Base58Check,
+    /// Bech32 encoding (BIP-173) with the configured human-readable part
+    Bech32,
+    /// Bech32m encoding (BIP-350) with the configured human-readable part
+    Bech32m,
+    /// Base32 encoding (RFC 4648)
+    Base32,
+    /// Base32 encoding with the extended hex alphabet (RFC 4648)
+    Base32Hex,
+    /// Crockford's Base32 encoding, decoded case-insensitively
+    Base32Crockford,
+    /// z-base-32 encoding
+    ZBase32,
+    /// User-defined encoding, not supported by the `serde` implementations
+    #[serde(skip)]
+    Custom(Arc<dyn BytesCodec>),
+}
+
+/// Integer encoding format
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IntegerFormat {
+    /// Default format (JSON number)
+    Default,
+    /// Ethereum JSON-RPC quantity: `0x`-prefixed hex without leading zeros (`0x0` for zero)
+    Quantity,
+    /// 64-bit and 128-bit integers as decimal strings
+    String,
+    /// 64-bit and 128-bit integers as decimal strings when outside the JavaScript safe
+    /// integer range ±(2^53 - 1), JSON numbers otherwise
+    SafeString,
+}
+
+/// Policy for non-finite floats (NaN, Infinity, -Infinity)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NonFiniteFloatPolicy {
+    /// Serializes non-finite floats as `null`, like `serde_json`
+    Null,
+    /// Fails to serialize non-finite floats
+    Error,
+    /// Serializes non-finite floats as the strings `"NaN"`, `"Infinity"` and `"-Infinity"`,
+    /// which are accepted back during deserialization
+    String,
+}
+
+/// Policy for the `0x` prefix when decoding hex values
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HexPrefixPolicy {
+    /// Accepts hex values with or without `0x` / `0X` prefix
+    Lenient,
+    /// Requires a lowercase `0x` prefix and an even number of digits
+    RequirePrefix,
+    /// Rejects any prefix and requires an even number of digits
+    ForbidPrefix,
+}
+
+/// Policy for the letter case of hex digits when decoding hex values
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HexCasePolicy {
+    /// Accepts lowercase, uppercase and mixed-case digits
+    Any,
+    /// Requires lowercase digits
+    Lowercase,
+    /// Requires uppercase digits
+    Uppercase,
+}
+
+/// Decoding mode for Base64 and Base64 URL-safe values
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Base64DecodeMode {
+    /// Only accepts the configured alphabet and padding
+    Strict,
+    /// Accepts padded or unpadded input in either alphabet, rejecting input that mixes both alphabets
+    Lenient,
+}
+
+/// Alphabet used for Base58 and Base58Check encoding
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Base58Alphabet {
+    /// Bitcoin alphabet (also used by Solana and IPFS)
+    Bitcoin,
+    /// Ripple alphabet
+    Ripple,
+    /// Flickr alphabet
+    Flickr,
+}
+
+impl Base58Alphabet {
+    pub(crate) fn as_bs58(self) -> &'static bs58::Alphabet {
+        match self {
+            Base58Alphabet::Bitcoin => bs58::Alphabet::BITCOIN,
+            Base58Alphabet::Ripple => bs58::Alphabet::RIPPLE,
+            Base58Alphabet::Flickr => bs58::Alphabet::FLICKR,
+        }
+    }
 }
 
 /// Configuration for serde_json operations
+///
+/// `Config` is `Send + Sync` and is only borrowed for the duration of a call, so it can
+/// be shared across threads and async tasks behind an `Arc<Config>` or in a `static`.
+///
+/// `Config` implements `Serialize` and `Deserialize` with the option names as keys (e.g.
+/// `bytes_format = "hex"`, `hex_prefix = true`), missing fields take their default value
+/// and unknown fields are rejected. Path, type and length rules are not included, a
+/// configuration with rules or a custom bytes format fails to serialize.
 #[derive(Debug, Clone)]
 pub struct Config {
     /// Bytes encoding format
     pub(crate) bytes_format: BytesFormat,
+    /// Bytes encoding format for serialization, overriding `bytes_format`
+    pub(crate) ser_bytes_format: Option<BytesFormat>,
+    /// Bytes encoding format for deserialization, overriding `bytes_format`
+    pub(crate) de_bytes_format: Option<BytesFormat>,
+    /// Integer encoding format
+    pub(crate) integer_format: IntegerFormat,
+    /// Policy for non-finite floats
+    pub(crate) non_finite_float: NonFiniteFloatPolicy,
     /// Enable EIP-55 checksum encoding for hex addresses
     pub(crate) hex_eip55: bool,
     /// Enable 0x prefix for hex values
     pub(crate) hex_prefix: bool,
+    /// Policy for the 0x prefix when decoding hex values
+    pub(crate) hex_prefix_policy: HexPrefixPolicy,
+    /// Enable uppercase digits for hex values
+    pub(crate) hex_uppercase: bool,
+    /// Policy for the letter case of hex digits when decoding hex values
+    pub(crate) hex_case_policy: HexCasePolicy,
+    /// Enable `=` padding for Base64 values
+    pub(crate) base64_padding: bool,
+    /// Decoding mode for Base64 values
+    pub(crate) base64_decode_mode: Base64DecodeMode,
+    /// Alphabet for Base58 and Base58Check values
+    pub(crate) base58_alphabet: Base58Alphabet,
+    /// Human-readable part for Bech32 and Bech32m values
+    pub(crate) bech32_hrp: String,
+    /// Enable `=` padding for RFC 4648 Base32 values
+    pub(crate) base32_padding: bool,
+    /// Enable lowercase output for RFC 4648 and Crockford Base32 values
+    pub(crate) base32_lowercase: bool,
+    /// Only decode bytes when the target asks for bytes, never guess them from strings
+    pub(crate) strict_bytes: bool,
+    /// Formats accepted when decoding bytes, in priority order, empty for the
+    /// deserialization format only
+    pub(crate) bytes_decode_formats: Vec<BytesFormat>,
+    /// Reject strings that decode to different bytes in several decode formats
+    pub(crate) bytes_ambiguity_error: bool,
+    /// Configurations applied to the values matching a JSON path
+    pub(crate) path_rules: Vec<PathRule>,
+    /// Configurations applied to the values of a newtype struct
+    pub(crate) type_rules: Vec<TypeRule>,
+    /// Expected byte lengths of the values of a newtype struct
+    pub(crate) length_rules: Vec<LengthRule>,
+    /// Expected byte lengths of the values matching a JSON path
+    pub(crate) path_length_rules: Vec<PathLengthRule>,
+}
+
+/// A configuration applied to the values matching a JSON path pattern
+#[derive(Debug, Clone)]
+pub(crate) struct PathRule {
+    pub(crate) path: String,
+    /// Parsed pattern, or the reason the pattern is malformed
+    pub(crate) pattern: Result<PathPattern, String>,
+    pub(crate) config: Config,
+}
+
+/// A configuration applied to the values of a newtype struct
+#[derive(Debug, Clone)]
+pub(crate) struct TypeRule {
+    pub(crate) name: String,
+    pub(crate) config: Config,
+}
+
+/// An expected byte length for the values of a newtype struct
+#[derive(Debug, Clone)]
+pub(crate) struct LengthRule {
+    pub(crate) name: String,
+    pub(crate) length: usize,
+}
+
+/// An expected byte length for the values matching a JSON path pattern
+#[derive(Debug, Clone)]
+pub(crate) struct PathLengthRule {
+    pub(crate) path: String,
+    /// Parsed pattern, or the reason the pattern is malformed
+    pub(crate) pattern: Result<PathPattern, String>,
+    pub(crate) length: usize,
+}
+
+/// A contradictory combination of options, returned by [`Config::validate`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigError {
+    /// An option is set that has no effect with the configured bytes format
+    FormatMismatch {
+        /// Name of the option
+        option: &'static str,
+        /// Configured bytes format for serialization
+        bytes_format: BytesFormat,
+    },
+    /// Two options contradict each other
+    Conflict {
+        /// Name of the option
+        option: &'static str,
+        /// Name of the contradicting option
+        other: &'static str,
+    },
+    /// The human-readable part is not valid for Bech32 and Bech32m
+    InvalidBech32Hrp(String),
+    /// The pattern of a path or path length rule is malformed, e.g. `$.tx..hash`
+    InvalidPathPattern {
+        /// Pattern of the rule
+        path: String,
+        /// Description of the error
+        reason: String,
+    },
+    /// The configuration of a path or type rule is invalid
+    Rule {
+        /// Path pattern or newtype name of the rule
+        rule: String,
+        /// Error of the rule configuration
+        error: Box<ConfigError>,
+    },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::FormatMismatch {
+                option,
+                bytes_format,
+            } => write!(
+                f,
+                "`{option}` has no effect with the {bytes_format:?} bytes format"
+            ),
+            ConfigError::Conflict { option, other } => {
+                write!(f, "`{option}` contradicts `{other}`")
+            }
+            ConfigError::InvalidBech32Hrp(hrp) => {
+                write!(f, "invalid bech32 human-readable part `{hrp}`")
+            }
+            ConfigError::InvalidPathPattern { path, reason } => {
+                write!(f, "invalid path pattern `{path}`: {reason}")
+            }
+            ConfigError::Rule { rule, error } => write!(f, "rule `{rule}`: {error}"),
+        }
+    }
 }
 
+impl std::error::Error for ConfigError {}
+
 impl Default for Config {
     fn default() -> Self {
         Config {
             bytes_format: BytesFormat::Default,
+            ser_bytes_format: None,
+            de_bytes_format: None,
+            integer_format: IntegerFormat::Default,
+            non_finite_float: NonFiniteFloatPolicy::Null,
             hex_eip55: false,
             hex_prefix: false,
+            hex_prefix_policy: HexPrefixPolicy::Lenient,
+            hex_uppercase: false,
+            hex_case_policy: HexCasePolicy::Any,
+            base64_padding: true,
+            base64_decode_mode: Base64DecodeMode::Strict,
+            base58_alphabet: Base58Alphabet::Bitcoin,
+            bech32_hrp: String::new(),
+            base32_padding: true,
+            base32_lowercase: false,
+            strict_bytes: false,
+            bytes_decode_formats: Vec::new(),
+            bytes_ambiguity_error: false,
+            path_rules: Vec::new(),
+            type_rules: Vec::new(),
+            length_rules: Vec::new(),
+            path_length_rules: Vec::new(),
+        }
+    }
+}
+
+/// The options of a [`Config`] as written to configuration files
+///
+/// Kept apart from `Config` so the file format doesn't follow its internal fields.
+#[derive(Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct Settings {
+    bytes_format: BytesFormat,
+    ser_bytes_format: Option<BytesFormat>,
+    de_bytes_format: Option<BytesFormat>,
+    integer_format: IntegerFormat,
+    non_finite_float: NonFiniteFloatPolicy,
+    hex_eip55: bool,
+    hex_prefix: bool,
+    hex_prefix_policy: HexPrefixPolicy,
+    hex_uppercase: bool,
+    hex_case_policy: HexCasePolicy,
+    base64_padding: bool,
+    base64_decode_mode: Base64DecodeMode,
+    base58_alphabet: Base58Alphabet,
+    bech32_hrp: String,
+    base32_padding: bool,
+    base32_lowercase: bool,
+    strict_bytes: bool,
+    bytes_decode_formats: Vec<BytesFormat>,
+    bytes_ambiguity_error: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings::from(&Config::default())
+    }
+}
+
+impl From<&Config> for Settings {
+    fn from(config: &Config) -> Self {
+        Settings {
+            bytes_format: config.bytes_format.clone(),
+            ser_bytes_format: config.ser_bytes_format.clone(),
+            de_bytes_format: config.de_bytes_format.clone(),
+            integer_format: config.integer_format,
+            non_finite_float: config.non_finite_float,
+            hex_eip55: config.hex_eip55,
+            hex_prefix: config.hex_prefix,
+            hex_prefix_policy: config.hex_prefix_policy,
+            hex_uppercase: config.hex_uppercase,
+            hex_case_policy: config.hex_case_policy,
+            base64_padding: config.base64_padding,
+            base64_decode_mode: config.base64_decode_mode,
+            base58_alphabet: config.base58_alphabet,
+            bech32_hrp: config.bech32_hrp.clone(),
+            base32_padding: config.base32_padding,
+            base32_lowercase: config.base32_lowercase,
+            strict_bytes: config.strict_bytes,
+            bytes_decode_formats: config.bytes_decode_formats.clone(),
+            bytes_ambiguity_error: config.bytes_ambiguity_error,
+        }
+    }
+}
+
+impl From<Settings> for Config {
+    fn from(settings: Settings) -> Self {
+        Config {
+            bytes_format: settings.bytes_format,
+            ser_bytes_format: settings.ser_bytes_format,
+            de_bytes_format: settings.de_bytes_format,
+            integer_format: settings.integer_format,
+            non_finite_float: settings.non_finite_float,
+            hex_eip55: settings.hex_eip55,
+            hex_prefix: settings.hex_prefix,
+            hex_prefix_policy: settings.hex_prefix_policy,
+            hex_uppercase: settings.hex_uppercase,
+            hex_case_policy: settings.hex_case_policy,
+            base64_padding: settings.base64_padding,
+            base64_decode_mode: settings.base64_decode_mode,
+            base58_alphabet: settings.base58_alphabet,
+            bech32_hrp: settings.bech32_hrp,
+            base32_padding: settings.base32_padding,
+            base32_lowercase: settings.base32_lowercase,
+            strict_bytes: settings.strict_bytes,
+            bytes_decode_formats: settings.bytes_decode_formats,
+            bytes_ambiguity_error: settings.bytes_ambiguity_error,
+            ..Config::default()
+        }
+    }
+}
+
+/// Sets an option of [`Settings`] from the value of an environment variable
+type SetOption = fn(&mut Settings, &str) -> Result<(), String>;
+
+/// Variable names read by [`Config::from_env`], after the prefix, and how they are parsed
+const ENV_OPTIONS: &[(&str, SetOption)] = &[
+    ("BYTES_FORMAT", |s, v| {
+        parse_enum(v).map(|v| s.bytes_format = v)
+    }),
+    ("SER_BYTES_FORMAT", |s, v| {
+        parse_enum(v).map(|v| s.ser_bytes_format = Some(v))
+    }),
+    ("DE_BYTES_FORMAT", |s, v| {
+        parse_enum(v).map(|v| s.de_bytes_format = Some(v))
+    }),
+    ("INTEGER_FORMAT", |s, v| {
+        parse_enum(v).map(|v| s.integer_format = v)
+    }),
+    ("NON_FINITE_FLOAT", |s, v| {
+        parse_enum(v).map(|v| s.non_finite_float = v)
+    }),
+    ("HEX_EIP55", |s, v| parse_bool(v).map(|v| s.hex_eip55 = v)),
+    ("HEX_PREFIX", |s, v| parse_bool(v).map(|v| s.hex_prefix = v)),
+    ("HEX_PREFIX_POLICY", |s, v| {
+        parse_enum(v).map(|v| s.hex_prefix_policy = v)
+    }),
+    ("HEX_UPPERCASE", |s, v| {
+        parse_bool(v).map(|v| s.hex_uppercase = v)
+    }),
+    ("HEX_CASE_POLICY", |s, v| {
+        parse_enum(v).map(|v| s.hex_case_policy = v)
+    }),
+    ("BASE64_PADDING", |s, v| {
+        parse_bool(v).map(|v| s.base64_padding = v)
+    }),
+    ("BASE64_DECODE_MODE", |s, v| {
+        parse_enum(v).map(|v| s.base64_decode_mode = v)
+    }),
+    ("BASE58_ALPHABET", |s, v| {
+        parse_enum(v).map(|v| s.base58_alphabet = v)
+    }),
+    ("BECH32_HRP", |s, v| {
+        s.bech32_hrp = v.to_string();
+        Ok(())
+    }),
+    ("BASE32_PADDING", |s, v| {
+        parse_bool(v).map(|v| s.base32_padding = v)
+    }),
+    ("BASE32_LOWERCASE", |s, v| {
+        parse_bool(v).map(|v| s.base32_lowercase = v)
+    }),
+    ("STRICT_BYTES", |s, v| {
+        parse_bool(v).map(|v| s.strict_bytes = v)
+    }),
+    ("BYTES_DECODE_FORMATS", |s, v| {
+        parse_list(v).map(|v| s.bytes_decode_formats = v)
+    }),
+    ("BYTES_AMBIGUITY_ERROR", |s, v| {
+        parse_bool(v).map(|v| s.bytes_ambiguity_error = v)
+    }),
+];
+
+fn parse_bool(value: &str) -> Result<bool, String> {
+    value
+        .parse()
+        .map_err(|_| format!("expected `true` or `false`, got `{value}`"))
+}
+
+/// Parses an enum variant by its snake case name, e.g. `base64_url_safe`
+fn parse_enum<T>(value: &str) -> Result<T, String>
+where
+    T: for<'de> Deserialize<'de>,
+{
+    let deserializer = serde::de::value::StrDeserializer::<serde::de::value::Error>::new(value);
+    T::deserialize(deserializer).map_err(|err| err.to_string())
+}
+
+/// Parses a comma-separated list of enum variants, ignoring empty items
+fn parse_list<T>(value: &str) -> Result<Vec<T>, String>
+where
+    T: for<'de> Deserialize<'de>,
+{
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(parse_enum)
+        .collect()
+}
+
+impl Serialize for Config {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        if !self.path_rules.is_empty()
+            || !self.type_rules.is_empty()
+            || !self.length_rules.is_empty()
+            || !self.path_length_rules.is_empty()
+        {
+            return Err(serde::ser::Error::custom(
+                "path, type and length rules can't be serialized",
+            ));
         }
+        Settings::from(self).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Config {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Settings::deserialize(deserializer).map(Config::from)
     }
 }
 
@@ -57,6 +531,204 @@ impl Config {
         self
     }
 
+    /// Enables `=` padding for Base64 values
+    pub fn enable_base64_padding(mut self) -> Self {
+        self.base64_padding = true;
+        self
+    }
+
+    /// Disables `=` padding for Base64 values
+    pub fn disable_base64_padding(mut self) -> Self {
+        self.base64_padding = false;
+        self
+    }
+
+    /// Sets the decoding mode for Base64 values
+    pub fn set_base64_decode_mode(mut self, mode: Base64DecodeMode) -> Self {
+        self.base64_decode_mode = mode;
+        self
+    }
+
+    /// Sets bytes format to Base58
+    pub fn set_bytes_base58(mut self) -> Self {
+        self.bytes_format = BytesFormat::Base58;
+        self
+    }
+
+    /// Sets bytes format to Base58Check
+    pub fn set_bytes_base58_check(mut self) -> Self {
+        self.bytes_format = BytesFormat::Base58Check;
+        self
+    }
+
+    /// Sets the alphabet for Base58 and Base58Check values
+    pub fn set_base58_alphabet(mut self, alphabet: Base58Alphabet) -> Self {
+        self.base58_alphabet = alphabet;
+        self
+    }
+
+    /// Sets bytes format to Bech32 with the given human-readable part (e.g. `cosmos`, `bc`)
+    pub fn set_bytes_bech32(mut self, hrp: &str) -> Self {
+        self.bytes_format = BytesFormat::Bech32;
+        self.bech32_hrp = hrp.to_string();
+        self
+    }
+
+    /// Sets bytes format to Bech32m with the given human-readable part
+    pub fn set_bytes_bech32m(mut self, hrp: &str) -> Self {
+        self.bytes_format = BytesFormat::Bech32m;
+        self.bech32_hrp = hrp.to_string();
+        self
+    }
+
+    /// Sets bytes format to Base32 (RFC 4648)
+    pub fn set_bytes_base32(mut self) -> Self {
+        self.bytes_format = BytesFormat::Base32;
+        self
+    }
+
+    /// Sets bytes format to Base32 with the extended hex alphabet (RFC 4648)
+    pub fn set_bytes_base32_hex(mut self) -> Self {
+        self.bytes_format = BytesFormat::Base32Hex;
+        self
+    }
+
+    /// Sets bytes format to Crockford's Base32
+    pub fn set_bytes_base32_crockford(mut self) -> Self {
+        self.bytes_format = BytesFormat::Base32Crockford;
+        self
+    }
+
+    /// Sets bytes format to z-base-32
+    pub fn set_bytes_z_base32(mut self) -> Self {
+        self.bytes_format = BytesFormat::ZBase32;
+        self
+    }
+
+    /// Sets bytes format to a user-defined encoding
+    pub fn set_bytes_custom(mut self, codec: Arc<dyn BytesCodec>) -> Self {
+        self.bytes_format = BytesFormat::Custom(codec);
+        self
+    }
+
+    /// Sets the bytes format for serialization only, overriding the bytes format
+    ///
+    /// With [`set_de_bytes_format`](Config::set_de_bytes_format) the two directions can
+    /// differ, e.g. writing Base64 while still reading hex from older clients.
+    pub fn set_ser_bytes_format(mut self, format: BytesFormat) -> Self {
+        self.ser_bytes_format = Some(format);
+        self
+    }
+
+    /// Sets the bytes format for deserialization only, overriding the bytes format
+    pub fn set_de_bytes_format(mut self, format: BytesFormat) -> Self {
+        self.de_bytes_format = Some(format);
+        self
+    }
+
+    /// Enables strict bytes decoding
+    ///
+    /// By default a string that decodes in the configured bytes format is handed to
+    /// self-describing targets (`#[serde(untagged)]`, `#[serde(flatten)]`,
+    /// `serde_json::Value`) as bytes, so with hex `"cafe"` arrives as `[0xca, 0xfe]`
+    /// instead of a string. Strict decoding only decodes bytes for `deserialize_bytes`
+    /// and `deserialize_byte_buf`, bytes fields under such targets then receive the raw
+    /// string.
+    pub fn enable_strict_bytes(mut self) -> Self {
+        self.strict_bytes = true;
+        self
+    }
+
+    /// Disables strict bytes decoding, strings are decoded as bytes when possible
+    pub fn disable_strict_bytes(mut self) -> Self {
+        self.strict_bytes = false;
+        self
+    }
+
+    /// Sets the formats accepted when deserializing bytes, in priority order
+    ///
+    /// Bytes are then decoded from an array of numbers or from a string in the first
+    /// format that accepts it, e.g. `[BytesFormat::Hex, BytesFormat::Base64,
+    /// BytesFormat::Base64UrlSafe]` for `0x` hex, bare hex, Base64 and URL-safe Base64.
+    /// The deserialization format is tried last when it is not listed, serialization is
+    /// not affected. An empty list restores decoding the deserialization format only.
+    pub fn set_bytes_decode_formats(mut self, formats: &[BytesFormat]) -> Self {
+        self.bytes_decode_formats = formats.to_vec();
+        self
+    }
+
+    /// Enables ambiguity errors for the decode formats
+    ///
+    /// A string that decodes to different bytes in several of the formats set with
+    /// [`set_bytes_decode_formats`](Config::set_bytes_decode_formats) is rejected instead of
+    /// taking the first format, e.g. `"cafe"` with hex and Base64. `0x`-prefixed strings
+    /// are taken as hex when hex is among the formats.
+    pub fn enable_bytes_ambiguity_error(mut self) -> Self {
+        self.bytes_ambiguity_error = true;
+        self
+    }
+
+    /// Disables ambiguity errors, the first decode format accepting a string wins
+    pub fn disable_bytes_ambiguity_error(mut self) -> Self {
+        self.bytes_ambiguity_error = false;
+        self
+    }
+
+    /// Enables `=` padding for RFC 4648 Base32 values
+    pub fn enable_base32_padding(mut self) -> Self {
+        self.base32_padding = true;
+        self
+    }
+
+    /// Disables `=` padding for RFC 4648 Base32 values
+    pub fn disable_base32_padding(mut self) -> Self {
+        self.base32_padding = false;
+        self
+    }
+
+    /// Enables lowercase output for RFC 4648 and Crockford Base32 values
+    pub fn enable_base32_lowercase(mut self) -> Self {
+        self.base32_lowercase = true;
+        self
+    }
+
+    /// Disables lowercase output for RFC 4648 and Crockford Base32 values
+    pub fn disable_base32_lowercase(mut self) -> Self {
+        self.base32_lowercase = false;
+        self
+    }
+
+    /// Sets integer format to default (JSON number)
+    pub fn set_integer_default(mut self) -> Self {
+        self.integer_format = IntegerFormat::Default;
+        self
+    }
+
+    /// Sets integer format to Ethereum JSON-RPC hex quantity ("0x1a")
+    pub fn set_integer_quantity(mut self) -> Self {
+        self.integer_format = IntegerFormat::Quantity;
+        self
+    }
+
+    /// Sets integer format to decimal strings for 64-bit and 128-bit integers
+    pub fn set_integer_string(mut self) -> Self {
+        self.integer_format = IntegerFormat::String;
+        self
+    }
+
+    /// Sets integer format to decimal strings for 64-bit and 128-bit integers outside
+    /// the JavaScript safe integer range
+    pub fn set_integer_safe_string(mut self) -> Self {
+        self.integer_format = IntegerFormat::SafeString;
+        self
+    }
+
+    /// Sets the policy for non-finite floats (NaN, Infinity, -Infinity)
+    pub fn set_non_finite_float_policy(mut self, policy: NonFiniteFloatPolicy) -> Self {
+        self.non_finite_float = policy;
+        self
+    }
+
     /// Enables EIP-55 checksum encoding for hex addresses
     pub fn enable_hex_eip55(mut self) -> Self {
         self.hex_eip55 = true;
@@ -80,4 +752,438 @@ impl Config {
         self.hex_prefix = false;
         self
     }
+
+    /// Sets the policy for the 0x prefix when decoding hex values
+    pub fn set_hex_prefix_policy(mut self, policy: HexPrefixPolicy) -> Self {
+        self.hex_prefix_policy = policy;
+        self
+    }
+
+    /// Enables uppercase digits for hex values
+    pub fn enable_hex_uppercase(mut self) -> Self {
+        self.hex_uppercase = true;
+        self
+    }
+
+    /// Disables uppercase digits for hex values
+    pub fn disable_hex_uppercase(mut self) -> Self {
+        self.hex_uppercase = false;
+        self
+    }
+
+    /// Sets the policy for the letter case of hex digits when decoding hex values
+    pub fn set_hex_case_policy(mut self, policy: HexCasePolicy) -> Self {
+        self.hex_case_policy = policy;
+        self
+    }
+
+    /// Adds a configuration for the values matching a JSON path pattern
+    ///
+    /// Patterns start at the document root `$`, e.g. `$.tx.signature`, `$.inputs[0].data`
+    /// or `$.tx.*.hash`, where `*` matches any single key or array index. The matching
+    /// configuration applies to the whole subtree below the value, rules are checked in
+    /// the order they were added and the first match wins. Rules of the added
+    /// configuration itself are ignored. Malformed patterns never match and are reported
+    /// by [`validate`](Config::validate).
+    pub fn add_path_rule(mut self, path: &str, config: Config) -> Self {
+        self.path_rules.push(PathRule {
+            path: path.to_string(),
+            pattern: PathPattern::parse(path),
+            config,
+        });
+        self
+    }
+
+    /// Adds a configuration for the values of the newtype struct with the given name
+    ///
+    /// The name is the one serde passes to `serialize_newtype_struct` and
+    /// `deserialize_newtype_struct`, i.e. the struct name unless renamed, e.g. `Address`
+    /// for `struct Address(#[serde(with = "serde_bytes")] Vec<u8>)`. The configuration
+    /// applies to the whole subtree below the value, path rules matching deeper
+    /// locations still take precedence. Rules of the added configuration itself are
+    /// ignored.
+    pub fn add_type_rule(mut self, name: &str, config: Config) -> Self {
+        self.type_rules.push(TypeRule {
+            name: name.to_string(),
+            config,
+        });
+        self
+    }
+
+    /// Requires the bytes of the newtype struct with the given name to have an exact length
+    ///
+    /// The name is matched like in [`add_type_rule`](Config::add_type_rule), e.g. `Hash` for
+    /// `struct Hash(#[serde(with = "serde_bytes")] [u8; 32])`. Deserializing a value of
+    /// another length fails with an error naming its location, e.g.
+    /// `expected 32 bytes, got 31 at $.block.hash`.
+    pub fn add_length_rule(mut self, name: &str, length: usize) -> Self {
+        self.length_rules.push(LengthRule {
+            name: name.to_string(),
+            length,
+        });
+        self
+    }
+
+    /// Requires the bytes of the values matching a JSON path pattern to have an exact length
+    ///
+    /// Patterns are written like in [`add_path_rule`](Config::add_path_rule), e.g.
+    /// `$.block.hash` for a `#[serde(with = "serde_bytes")] hash: [u8; 32]` field, and the
+    /// first matching rule applies. Length rules of a newtype at the same location take
+    /// precedence.
+    pub fn add_path_length_rule(mut self, path: &str, length: usize) -> Self {
+        self.path_length_rules.push(PathLengthRule {
+            path: path.to_string(),
+            pattern: PathPattern::parse(path),
+            length,
+        });
+        self
+    }
+}
+
+impl Config {
+    /// Returns the bytes encoding format
+    pub fn bytes_format(&self) -> &BytesFormat {
+        &self.bytes_format
+    }
+
+    /// Returns the bytes format used for serialization
+    pub fn ser_bytes_format(&self) -> &BytesFormat {
+        self.ser_bytes_format.as_ref().unwrap_or(&self.bytes_format)
+    }
+
+    /// Returns the bytes format used for deserialization
+    pub fn de_bytes_format(&self) -> &BytesFormat {
+        self.de_bytes_format.as_ref().unwrap_or(&self.bytes_format)
+    }
+
+    /// Returns the integer encoding format
+    pub fn integer_format(&self) -> IntegerFormat {
+        self.integer_format
+    }
+
+    /// Returns the policy for non-finite floats
+    pub fn non_finite_float_policy(&self) -> NonFiniteFloatPolicy {
+        self.non_finite_float
+    }
+
+    /// Returns true when EIP-55 checksum encoding is enabled
+    pub fn hex_eip55(&self) -> bool {
+        self.hex_eip55
+    }
+
+    /// Returns true when the 0x prefix is enabled for hex values
+    pub fn hex_prefix(&self) -> bool {
+        self.hex_prefix
+    }
+
+    /// Returns the policy for the 0x prefix when decoding hex values
+    pub fn hex_prefix_policy(&self) -> HexPrefixPolicy {
+        self.hex_prefix_policy
+    }
+
+    /// Returns true when uppercase hex digits are enabled
+    pub fn hex_uppercase(&self) -> bool {
+        self.hex_uppercase
+    }
+
+    /// Returns the policy for the letter case of hex digits when decoding hex values
+    pub fn hex_case_policy(&self) -> HexCasePolicy {
+        self.hex_case_policy
+    }
+
+    /// Returns true when `=` padding is enabled for Base64 values
+    pub fn base64_padding(&self) -> bool {
+        self.base64_padding
+    }
+
+    /// Returns the decoding mode for Base64 values
+    pub fn base64_decode_mode(&self) -> Base64DecodeMode {
+        self.base64_decode_mode
+    }
+
+    /// Returns the alphabet for Base58 and Base58Check values
+    pub fn base58_alphabet(&self) -> Base58Alphabet {
+        self.base58_alphabet
+    }
+
+    /// Returns the human-readable part for Bech32 and Bech32m values
+    pub fn bech32_hrp(&self) -> &str {
+        &self.bech32_hrp
+    }
+
+    /// Returns true when `=` padding is enabled for RFC 4648 Base32 values
+    pub fn base32_padding(&self) -> bool {
+        self.base32_padding
+    }
+
+    /// Returns true when lowercase output is enabled for RFC 4648 and Crockford Base32 values
+    pub fn base32_lowercase(&self) -> bool {
+        self.base32_lowercase
+    }
+
+    /// Returns true when strict bytes decoding is enabled
+    pub fn strict_bytes(&self) -> bool {
+        self.strict_bytes
+    }
+
+    /// Returns the formats accepted when decoding bytes, in priority order
+    pub fn bytes_decode_formats(&self) -> &[BytesFormat] {
+        &self.bytes_decode_formats
+    }
+
+    /// Returns true when ambiguity errors are enabled for the decode formats
+    pub fn bytes_ambiguity_error(&self) -> bool {
+        self.bytes_ambiguity_error
+    }
+
+    /// Returns the path patterns and configurations of the path rules, in order
+    pub fn path_rules(&self) -> impl Iterator<Item = (&str, &Config)> {
+        self.path_rules
+            .iter()
+            .map(|rule| (rule.path.as_str(), &rule.config))
+    }
+
+    /// Returns the newtype names and configurations of the type rules, in order
+    pub fn type_rules(&self) -> impl Iterator<Item = (&str, &Config)> {
+        self.type_rules
+            .iter()
+            .map(|rule| (rule.name.as_str(), &rule.config))
+    }
+
+    /// Returns the newtype names and expected byte lengths of the length rules, in order
+    pub fn length_rules(&self) -> impl Iterator<Item = (&str, usize)> {
+        self.length_rules
+            .iter()
+            .map(|rule| (rule.name.as_str(), rule.length))
+    }
+
+    /// Returns the path patterns and expected byte lengths of the path length rules, in order
+    pub fn path_length_rules(&self) -> impl Iterator<Item = (&str, usize)> {
+        self.path_length_rules
+            .iter()
+            .map(|rule| (rule.path.as_str(), rule.length))
+    }
+
+    /// Checks the configuration and its rules for contradictory options
+    ///
+    /// Rejects options set for other bytes formats than the configured one (e.g. EIP-55
+    /// with Base64), options that prevent reading back the serialized output (e.g. a hex
+    /// prefix with [`HexPrefixPolicy::ForbidPrefix`]) and invalid Bech32 human-readable
+    /// parts. Output options are checked against the serialization format, decoding
+    /// options against the deserialization and decode formats. Marker types use the
+    /// options of their own format, so configurations that set them for markers only are
+    /// rejected as well.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        use BytesFormat::*;
+
+        let format = self.ser_bytes_format();
+        let de_format = self.de_bytes_format();
+        let decodes = |applies: fn(&BytesFormat) -> bool| {
+            applies(format) || applies(de_format) || self.bytes_decode_formats.iter().any(applies)
+        };
+        let hex = matches!(format, Hex);
+        let hex_decode = decodes(|format| matches!(format, Hex));
+        let base64 = matches!(format, Base64 | Base64UrlSafe);
+        let base64_decode = decodes(|format| matches!(format, Base64 | Base64UrlSafe));
+        let base58 = decodes(|format| matches!(format, Base58 | Base58Check));
+        let bech32 = decodes(|format| matches!(format, Bech32 | Bech32m));
+
+        let options = [
+            ("hex_eip55", self.hex_eip55, hex),
+            ("hex_prefix", self.hex_prefix, hex),
+            ("hex_uppercase", self.hex_uppercase, hex),
+            (
+                "hex_prefix_policy",
+                self.hex_prefix_policy != HexPrefixPolicy::Lenient,
+                hex_decode,
+            ),
+            (
+                "hex_case_policy",
+                self.hex_case_policy != HexCasePolicy::Any,
+                hex_decode,
+            ),
+            ("base64_padding", !self.base64_padding, base64),
+            (
+                "base64_decode_mode",
+                self.base64_decode_mode != Base64DecodeMode::Strict,
+                base64_decode,
+            ),
+            (
+                "base58_alphabet",
+                self.base58_alphabet != Base58Alphabet::Bitcoin,
+                base58,
+            ),
+            ("bech32_hrp", !self.bech32_hrp.is_empty(), bech32),
+            (
+                "base32_padding",
+                !self.base32_padding,
+                decodes(|format| matches!(format, Base32 | Base32Hex)),
+            ),
+            (
+                "base32_lowercase",
+                self.base32_lowercase,
+                decodes(|format| matches!(format, Base32 | Base32Hex | Base32Crockford)),
+            ),
+        ];
+        if let Some((option, _, _)) = options.iter().find(|(_, set, applies)| *set && !applies) {
+            return Err(ConfigError::FormatMismatch {
+                option,
+                bytes_format: format.clone(),
+            });
+        }
+
+        let uppercase_mismatch = if self.hex_uppercase {
+            HexCasePolicy::Lowercase
+        } else {
+            HexCasePolicy::Uppercase
+        };
+        let prefix_mismatch = if self.hex_prefix {
+            HexPrefixPolicy::ForbidPrefix
+        } else {
+            HexPrefixPolicy::RequirePrefix
+        };
+        let conflicts = [
+            (
+                "hex_eip55",
+                "hex_uppercase",
+                self.hex_eip55 && self.hex_uppercase,
+            ),
+            (
+                "hex_eip55",
+                "hex_case_policy",
+                self.hex_eip55 && self.hex_case_policy != HexCasePolicy::Any,
+            ),
+            (
+                "hex_uppercase",
+                "hex_case_policy",
+                hex && self.hex_case_policy == uppercase_mismatch,
+            ),
+            (
+                "hex_prefix",
+                "hex_prefix_policy",
+                hex && self.hex_prefix_policy == prefix_mismatch,
+            ),
+        ];
+        if let Some((option, other, _)) = conflicts.iter().find(|(_, _, conflict)| *conflict) {
+            return Err(ConfigError::Conflict { option, other });
+        }
+
+        if bech32 && bech32::Hrp::parse(&self.bech32_hrp).is_err() {
+            return Err(ConfigError::InvalidBech32Hrp(self.bech32_hrp.clone()));
+        }
+
+        let patterns = self
+            .path_rules
+            .iter()
+            .map(|rule| (&rule.path, &rule.pattern));
+        let length_patterns = self
+            .path_length_rules
+            .iter()
+            .map(|rule| (&rule.path, &rule.pattern));
+        for (path, pattern) in patterns.chain(length_patterns) {
+            if let Err(reason) = pattern {
+                return Err(ConfigError::InvalidPathPattern {
+                    path: path.clone(),
+                    reason: reason.clone(),
+                });
+            }
+        }
+
+        let rules = self.path_rules().chain(self.type_rules());
+        for (rule, config) in rules {
+            config.validate().map_err(|error| ConfigError::Rule {
+                rule: rule.to_string(),
+                error: Box::new(error),
+            })?;
+        }
+
+        Ok(())
+    }
+
+    /// Creates a configuration from environment variables
+    ///
+    /// Each field is read from the variable named after the prefix and the upper-case
+    /// field name, e.g. `APP_BYTES_FORMAT=hex` and `APP_HEX_PREFIX=true` for the prefix
+    /// `APP`. Lists are comma-separated (`APP_BYTES_DECODE_FORMATS=hex,base64`). Unset
+    /// variables keep their default value.
+    pub fn from_env(prefix: &str) -> serde_json::Result<Self> {
+        Config::from_env_with(prefix, |name| {
+            env::var_os(name).map(|value| value.to_string_lossy().into_owned())
+        })
+    }
+
+    /// Creates a configuration from variables returned by `get`
+    ///
+    /// Reads the same variable names as [`from_env`](Config::from_env), e.g. from a
+    /// `.env` file or a map, `get` returns `None` for unset variables.
+    pub fn from_env_with(
+        prefix: &str,
+        get: impl Fn(&str) -> Option<String>,
+    ) -> serde_json::Result<Self> {
+        let mut settings = Settings::default();
+        for (option, set) in ENV_OPTIONS {
+            let name = format!("{prefix}_{option}");
+            let Some(value) = get(&name) else {
+                continue;
+            };
+            set(&mut settings, &value)
+                .map_err(|err| serde_json::Error::custom(format!("{name}: {err}")))?;
+        }
+
+        Ok(Config::from(settings))
+    }
+
+    /// Preset for Ethereum JSON-RPC
+    ///
+    /// Bytes as `0x`-prefixed hex with EIP-55 checksums for 20-byte addresses, integers
+    /// as hex quantities (`"0x1b4"`).
+    pub fn ethereum() -> Self {
+        Config::default()
+            .set_bytes_hex()
+            .enable_hex_prefix()
+            .enable_hex_eip55()
+            .set_integer_quantity()
+    }
+
+    /// Preset for Solana
+    ///
+    /// Bytes as Base58 with the Bitcoin alphabet, integers as JSON numbers.
+    pub fn solana() -> Self {
+        Config::default().set_bytes_base58()
+    }
+
+    /// Preset for Cosmos SDK
+    ///
+    /// Bytes as padded standard Base64, 64-bit and 128-bit integers as decimal strings.
+    pub fn cosmos() -> Self {
+        Config::default().set_bytes_base64().set_integer_string()
+    }
+
+    /// Preset for web clients
+    ///
+    /// Bytes as unpadded URL-safe Base64, integers outside the JavaScript safe integer
+    /// range as decimal strings.
+    pub fn web_safe() -> Self {
+        Config::default()
+            .set_bytes_base64_url_safe()
+            .disable_base64_padding()
+            .set_integer_safe_string()
+    }
+}
+
+impl Config {
+    /// Returns the `base32` alphabet for a Base32 format
+    ///
+    /// Formats other than the Base32 variants map to the RFC 4648 alphabet.
+    pub(crate) fn base32_alphabet(&self, format: &BytesFormat) -> base32::Alphabet {
+        let padding = self.base32_padding;
+        match (format, self.base32_lowercase) {
+            (BytesFormat::Base32Hex, false) => base32::Alphabet::Rfc4648Hex { padding },
+            (BytesFormat::Base32Hex, true) => base32::Alphabet::Rfc4648HexLower { padding },
+            (BytesFormat::Base32Crockford, _) => base32::Alphabet::Crockford,
+            (BytesFormat::ZBase32, _) => base32::Alphabet::Z,
+            (_, false) => base32::Alphabet::Rfc4648 { padding },
+            (_, true) => base32::Alphabet::Rfc4648Lower { padding },
+        }
+    }
 }
@@ -0,0 +1,124 @@
+// Traversal state shared by the serializer and deserializer wrappers
+
+use crate::{
+    Config,
+    path::{Path, Segment},
+};
+
+/// The configuration in effect at a location in the document
+#[derive(Debug, Clone)]
+pub(crate) struct Context<'a> {
+    /// Configuration passed by the caller, holding the rules
+    pub(crate) root: &'a Config,
+    /// Configuration applied to the current value
+    pub(crate) config: &'a Config,
+    /// Location of the current value, only tracked when the root has path or length rules
+    pub(crate) path: Path,
+    /// Expected byte length of the current value, set by length and path length rules
+    pub(crate) length: Option<usize>,
+    /// The current value is an object key, which JSON always writes as a string
+    pub(crate) key: bool,
+}
+
+impl<'a> Context<'a> {
+    /// Creates a context for the document root
+    pub(crate) fn new(config: &'a Config) -> Self {
+        Context {
+            root: config,
+            config,
+            path: Path::default(),
+            length: None,
+            key: false,
+        }
+    }
+
+    /// Returns true when locations must be tracked to apply rules or report errors
+    pub(crate) fn tracks_path(&self) -> bool {
+        !self.root.path_rules.is_empty()
+            || !self.root.length_rules.is_empty()
+            || !self.root.path_length_rules.is_empty()
+    }
+
+    /// Returns the context of an object member
+    pub(crate) fn key(&self, key: &str) -> Self {
+        self.descend(|| Segment::Key(key.to_string()))
+    }
+
+    /// Returns the context of the key of an object member
+    pub(crate) fn map_key(&self) -> Self {
+        Context {
+            key: true,
+            ..self.clone()
+        }
+    }
+
+    /// Returns the context of an array element
+    pub(crate) fn index(&self, index: usize) -> Self {
+        self.descend(|| Segment::Index(index))
+    }
+
+    /// Returns the context of the value inside a newtype struct
+    pub(crate) fn newtype(&self, name: &str) -> Self {
+        let config = self
+            .root
+            .type_rules
+            .iter()
+            .find(|rule| rule.name == name)
+            .map_or(self.config, |rule| &rule.config);
+        let length = self
+            .root
+            .length_rules
+            .iter()
+            .find(|rule| rule.name == name)
+            .map(|rule| rule.length)
+            .or(self.length);
+
+        Context {
+            root: self.root,
+            config,
+            path: self.path.clone(),
+            length,
+            key: self.key,
+        }
+    }
+
+    fn descend(&self, segment: impl FnOnce() -> Segment) -> Self {
+        if !self.tracks_path() {
+            return Context {
+                length: None,
+                key: false,
+                ..self.clone()
+            };
+        }
+
+        let path = self.path.push(segment());
+        let config = self
+            .root
+            .path_rules
+            .iter()
+            .find(|rule| {
+                rule.pattern
+                    .as_ref()
+                    .is_ok_and(|pattern| pattern.matches(&path))
+            })
+            .map_or(self.config, |rule| &rule.config);
+        let length = self
+            .root
+            .path_length_rules
+            .iter()
+            .find(|rule| {
+                rule.pattern
+                    .as_ref()
+                    .is_ok_and(|pattern| pattern.matches(&path))
+            })
+            .map(|rule| rule.length);
+
+        Context {
+            root: self.root,
+            config,
+            path,
+            length,
+            key: false,
+        }
+    }
+}
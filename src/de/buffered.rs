@@ -0,0 +1,186 @@
+// Rules for values that serde buffers before deserializing them
+//
+// `#[serde(flatten)]`, `#[serde(untagged)]` and internally tagged enums read their input
+// through `deserialize_any` into a buffer and deserialize the fields from that buffer
+// without the wrapper. The wrapper records the rules in effect where each buffered string
+// was read, so the marker types can apply them when they decode the value later.
+
+use std::{
+    cell::RefCell,
+    collections::{HashMap, hash_map},
+    ptr,
+    sync::Arc,
+};
+
+use crate::{Config, context::Context, path::Path};
+
+thread_local! {
+    static CALL: RefCell<Option<Call>> = const { RefCell::new(None) };
+}
+
+/// State of the running deserialization call
+struct Call {
+    /// Configuration passed to the deserialization function
+    root: Arc<Config>,
+    /// Address of the caller's configuration, to ignore wrappers created with another one
+    origin: *const Config,
+    /// Strings read through `deserialize_any`
+    strs: HashMap<String, Entry>,
+}
+
+/// The configuration applied at a location, as a rule of the root configuration
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Rule {
+    Root,
+    Path(usize),
+    Type(usize),
+}
+
+/// A setting in effect where a buffered value was read
+#[derive(Debug, Clone)]
+enum Located<T> {
+    /// The setting of every location the value was read at, and the first of them
+    At(T, Path),
+    /// The value was read at two locations with different settings
+    Ambiguous(Path, Path),
+}
+
+impl<T: PartialEq> Located<T> {
+    fn merge(&mut self, value: T, path: &Path) {
+        if let Located::At(other, other_path) = self
+            && *other != value
+        {
+            *self = Located::Ambiguous(other_path.clone(), path.clone());
+        }
+    }
+
+    fn get(&self) -> Result<(&T, &Path), String> {
+        match self {
+            Located::At(value, path) => Ok((value, path)),
+            Located::Ambiguous(first, second) => Err(format!(
+                "buffered value is read at {first} and {second} with different rules"
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Entry {
+    rule: Located<Rule>,
+    length: Located<Option<usize>>,
+}
+
+/// The rules in effect where a buffered value was read
+pub(crate) struct Location {
+    root: Arc<Config>,
+    entry: Option<Entry>,
+}
+
+impl Location {
+    /// Returns the configuration applied to the value
+    pub(crate) fn config(&self) -> Result<&Config, String> {
+        let Some(entry) = &self.entry else {
+            return Ok(&self.root);
+        };
+        Ok(match *entry.rule.get()?.0 {
+            Rule::Root => &self.root,
+            Rule::Path(index) => &self.root.path_rules[index].config,
+            Rule::Type(index) => &self.root.type_rules[index].config,
+        })
+    }
+
+    /// Returns the expected byte length of the value and where it was read, `None` when no
+    /// length rule applies
+    pub(crate) fn length(&self) -> Result<Option<(usize, &Path)>, String> {
+        let Some(entry) = &self.entry else {
+            return Ok(None);
+        };
+        let (length, path) = entry.length.get()?;
+        Ok(length.map(|length| (length, path)))
+    }
+}
+
+/// Runs a deserialization call, recording the rules of the values serde buffers
+///
+/// Unlike a [`with_config`](crate::with_config) scope, [`current_config`](crate::current_config)
+/// is unchanged inside `Deserialize` impls.
+pub(crate) fn with_call<F, R>(config: &Config, f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    struct Restore(Option<Call>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            let previous = self.0.take();
+            CALL.with(|call| *call.borrow_mut() = previous);
+        }
+    }
+
+    let call = Call {
+        root: Arc::new(config.clone()),
+        origin: config,
+        strs: HashMap::new(),
+    };
+    let previous = CALL.with(|current| current.borrow_mut().replace(call));
+    let _restore = Restore(previous);
+
+    f()
+}
+
+/// Records the rules in effect where a string is read through `deserialize_any`
+pub(crate) fn record_str(ctx: &Context<'_>, value: &str) {
+    let root = ctx.root;
+    if root.type_rules.is_empty() && !ctx.tracks_path() {
+        return;
+    }
+
+    CALL.with(|call| {
+        let mut call = call.borrow_mut();
+        let Some(call) = call.as_mut().filter(|call| ptr::eq(call.origin, root)) else {
+            return;
+        };
+
+        let rule = if ptr::eq(ctx.config, root) {
+            Rule::Root
+        } else if let Some(index) =
+            (root.path_rules.iter()).position(|rule| ptr::eq(&rule.config, ctx.config))
+        {
+            Rule::Path(index)
+        } else if let Some(index) =
+            (root.type_rules.iter()).position(|rule| ptr::eq(&rule.config, ctx.config))
+        {
+            Rule::Type(index)
+        } else {
+            return;
+        };
+
+        match call.strs.entry(value.to_string()) {
+            hash_map::Entry::Vacant(entry) => {
+                entry.insert(Entry {
+                    rule: Located::At(rule, ctx.path.clone()),
+                    length: Located::At(ctx.length, ctx.path.clone()),
+                });
+            }
+            hash_map::Entry::Occupied(mut entry) => {
+                let entry = entry.get_mut();
+                entry.rule.merge(rule, &ctx.path);
+                entry.length.merge(ctx.length, &ctx.path);
+            }
+        }
+    });
+}
+
+/// Returns the rules in effect where a buffered string was read, `None` outside of a
+/// deserialization call
+pub(crate) fn locate_str(value: &str) -> Option<Location> {
+    CALL.with(|call| {
+        let call = call.borrow();
+        let call = call.as_ref()?;
+
+        Some(Location {
+            root: call.root.clone(),
+            entry: call.strs.get(value).cloned(),
+        })
+    })
+}
@@ -1,6 +1,8 @@
 // Bytes deserialization utilities
 
-use crate::{BytesFormat, Config};
+use crate::{
+    Base64DecodeMode, BytesCodec, BytesFormat, Config, HexCasePolicy, HexPrefixPolicy, eip55,
+};
 use serde::de::Visitor;
 
 /// Deserializes bytes from JSON format based on the configuration
@@ -23,114 +25,238 @@ where
     D: serde::de::Deserializer<'de>,
     V: Visitor<'de>,
 {
-    match config.bytes_format {
-        BytesFormat::Default => de_bytes_array(deserializer, visitor),
-        BytesFormat::Hex => de_bytes_hex(deserializer, config, visitor),
-        BytesFormat::Base64 => de_bytes_base64(deserializer, false, visitor),
-        BytesFormat::Base64UrlSafe => de_bytes_base64(deserializer, true, visitor),
+    if !config.bytes_decode_formats.is_empty() {
+        return de_bytes_lenient(deserializer, config, visitor);
     }
+    de_bytes_as(deserializer, config, config.de_bytes_format(), visitor)
 }
 
-/// Deserializes bytes from a JSON array of numbers [1, 2, 3]
-pub(crate) fn de_bytes_array<'de, D, V>(deserializer: D, visitor: V) -> Result<V::Value, D::Error>
+/// Deserializes bytes from JSON in the given format, using the other settings of the configuration
+///
+/// # Arguments
+///
+/// * `deserializer` - A `serde_json::de::Deserializer` (consumed)
+/// * `config` - Configuration for the settings of the format
+/// * `format` - The bytes format to decode
+/// * `visitor` - A visitor that implements `Visitor<'de>`
+///
+/// # Returns
+///
+/// Returns `Result<V::Value, serde_json::Error>` indicating success or failure
+pub(crate) fn de_bytes_as<'de, D, V>(
+    deserializer: D,
+    config: &Config,
+    format: &BytesFormat,
+    visitor: V,
+) -> Result<V::Value, D::Error>
 where
     D: serde::de::Deserializer<'de>,
     V: Visitor<'de>,
 {
-    deserializer.deserialize_bytes(visitor)
+    match format {
+        BytesFormat::Default => de_bytes_array(deserializer, visitor),
+        BytesFormat::Hex => de_bytes_hex(deserializer, config, visitor),
+        BytesFormat::Base64 => de_bytes_base64(deserializer, config, false, visitor),
+        BytesFormat::Base64UrlSafe => de_bytes_base64(deserializer, config, true, visitor),
+        BytesFormat::Base58 => de_bytes_base58(deserializer, config, false, visitor),
+        BytesFormat::Base58Check => de_bytes_base58(deserializer, config, true, visitor),
+        BytesFormat::Bech32 => de_bytes_bech32(deserializer, config, false, visitor),
+        BytesFormat::Bech32m => de_bytes_bech32(deserializer, config, true, visitor),
+        BytesFormat::Base32
+        | BytesFormat::Base32Hex
+        | BytesFormat::Base32Crockford
+        | BytesFormat::ZBase32 => de_bytes_base32(deserializer, config, format, visitor),
+        BytesFormat::Custom(codec) => de_bytes_custom(deserializer, codec.as_ref(), visitor),
+    }
 }
 
-/// Deserializes bytes from a hexadecimal string "0x1234..." or "1234..."
-pub(crate) fn de_bytes_hex<'de, D, V>(
+/// Decodes a string into bytes based on the configuration
+///
+/// Returns `None` when the configured format is not string based.
+pub(crate) fn decode_str(config: &Config, value: &str) -> Option<Result<Vec<u8>, String>> {
+    if !config.bytes_decode_formats.is_empty() {
+        return Some(decode_lenient(config, value));
+    }
+    decode_str_as(config, config.de_bytes_format(), value)
+}
+
+/// Decodes a string into bytes in the given format, using the other settings of the
+/// configuration
+///
+/// Returns `None` when the format is not string based.
+pub(crate) fn decode_str_as(
+    config: &Config,
+    format: &BytesFormat,
+    value: &str,
+) -> Option<Result<Vec<u8>, String>> {
+    match format {
+        BytesFormat::Default => None,
+        BytesFormat::Hex => Some(decode_hex(config, value)),
+        BytesFormat::Base64 => Some(decode_base64(config, value, false)),
+        BytesFormat::Base64UrlSafe => Some(decode_base64(config, value, true)),
+        BytesFormat::Base58 => Some(decode_base58(config, value, false)),
+        BytesFormat::Base58Check => Some(decode_base58(config, value, true)),
+        BytesFormat::Bech32 => Some(decode_bech32(config, value, false)),
+        BytesFormat::Bech32m => Some(decode_bech32(config, value, true)),
+        BytesFormat::Base32
+        | BytesFormat::Base32Hex
+        | BytesFormat::Base32Crockford
+        | BytesFormat::ZBase32 => Some(decode_base32(config, value, format)),
+        BytesFormat::Custom(codec) => Some(decode_custom(codec.as_ref(), value)),
+    }
+}
+
+/// Deserializes bytes from a JSON array of numbers or a string in any of the decode formats
+fn de_bytes_lenient<'de, D, V>(
     deserializer: D,
-    _config: &Config,
+    config: &Config,
     visitor: V,
 ) -> Result<V::Value, D::Error>
 where
     D: serde::de::Deserializer<'de>,
     V: Visitor<'de>,
 {
-    struct HexBytesVisitor<V> {
+    struct LenientBytesVisitor<'c, V> {
+        config: &'c Config,
         visitor: V,
     }
 
-    impl<'de, V> Visitor<'de> for HexBytesVisitor<V>
+    impl<'de, V> Visitor<'de> for LenientBytesVisitor<'_, V>
     where
         V: Visitor<'de>,
     {
         type Value = V::Value;
 
         fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
-            formatter.write_str("a hexadecimal string")
+            formatter.write_str("an encoded byte string or an array of bytes")
         }
 
         fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
         where
             E: serde::de::Error,
         {
-            let hex_str = if v.starts_with("0x") || v.starts_with("0X") {
-                &v[2..]
-            } else {
-                v
-            };
-            let bytes = hex::decode(hex_str)
-                .map_err(|e| E::custom(format!("invalid hex string: {}", e)))?;
-            self.visitor.visit_bytes(&bytes)
+            let bytes = decode_lenient(self.config, v).map_err(E::custom)?;
+            self.visitor.visit_byte_buf(bytes)
         }
 
-        fn visit_string<E>(self, v: String) -> Result<Self::Value, E>
+        fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
         where
             E: serde::de::Error,
         {
-            self.visit_str(&v)
+            self.visitor.visit_bytes(v)
+        }
+
+        fn visit_byte_buf<E>(self, v: Vec<u8>) -> Result<Self::Value, E>
+        where
+            E: serde::de::Error,
+        {
+            self.visitor.visit_byte_buf(v)
+        }
+
+        fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+        where
+            A: serde::de::SeqAccess<'de>,
+        {
+            let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+            while let Some(byte) = seq.next_element()? {
+                bytes.push(byte);
+            }
+            self.visitor.visit_byte_buf(bytes)
         }
     }
 
-    deserializer.deserialize_str(HexBytesVisitor { visitor })
+    deserializer.deserialize_any(LenientBytesVisitor { config, visitor })
 }
 
-/// Deserializes bytes from a Base64 string
+/// Decodes a string in the first decode format that accepts it, in priority order
 ///
-/// # Arguments
-///
-/// * `url_safe` - If true, uses URL-safe Base64 decoding, otherwise uses standard Base64
-pub(crate) fn de_bytes_base64<'de, D, V>(
+/// The deserialization format is tried last when it is not among the decode formats. With
+/// ambiguity errors enabled, a string that decodes to different bytes in several formats
+/// is rejected, except `0x`-prefixed strings, which are taken as hex when hex accepts
+/// them.
+pub(crate) fn decode_lenient(config: &Config, value: &str) -> Result<Vec<u8>, String> {
+    let fallback = Some(config.de_bytes_format())
+        .filter(|format| !config.bytes_decode_formats.contains(format));
+    let formats: Vec<_> = config.bytes_decode_formats.iter().chain(fallback).collect();
+    let mut decoded =
+        formats
+            .iter()
+            .copied()
+            .filter_map(|format| match decode_str_as(config, format, value) {
+                Some(Ok(bytes)) => Some((format, bytes)),
+                _ => None,
+            });
+
+    if !config.bytes_ambiguity_error {
+        return decoded
+            .next()
+            .map(|(_, bytes)| bytes)
+            .ok_or_else(|| format!("invalid bytes string, expected one of {formats:?}"));
+    }
+
+    let decoded: Vec<_> = decoded.collect();
+    let prefixed = value.starts_with("0x") || value.starts_with("0X");
+    if let Some((_, bytes)) = decoded
+        .iter()
+        .find(|(format, _)| prefixed && **format == BytesFormat::Hex)
+    {
+        return Ok(bytes.clone());
+    }
+
+    match decoded.split_first() {
+        None => Err(format!("invalid bytes string, expected one of {formats:?}")),
+        Some(((format, bytes), rest)) => match rest.iter().find(|(_, other)| other != bytes) {
+            Some((other, _)) => Err(format!(
+                "ambiguous bytes string, valid as {format:?} and {other:?}"
+            )),
+            None => Ok(bytes.clone()),
+        },
+    }
+}
+
+/// Deserializes bytes from a JSON array of numbers [1, 2, 3]
+pub(crate) fn de_bytes_array<'de, D, V>(deserializer: D, visitor: V) -> Result<V::Value, D::Error>
+where
+    D: serde::de::Deserializer<'de>,
+    V: Visitor<'de>,
+{
+    deserializer.deserialize_bytes(visitor)
+}
+
+/// Deserializes bytes from a string, decoding it with `decode`
+fn de_bytes_str<'de, D, V, F>(
     deserializer: D,
-    url_safe: bool,
+    expecting: &'static str,
+    decode: F,
     visitor: V,
 ) -> Result<V::Value, D::Error>
 where
     D: serde::de::Deserializer<'de>,
     V: Visitor<'de>,
+    F: FnOnce(&str) -> Result<Vec<u8>, String>,
 {
-    struct Base64BytesVisitor<V> {
-        url_safe: bool,
+    struct StrBytesVisitor<V, F> {
+        expecting: &'static str,
+        decode: F,
         visitor: V,
     }
 
-    impl<'de, V> Visitor<'de> for Base64BytesVisitor<V>
+    impl<'de, V, F> Visitor<'de> for StrBytesVisitor<V, F>
     where
         V: Visitor<'de>,
+        F: FnOnce(&str) -> Result<Vec<u8>, String>,
     {
         type Value = V::Value;
 
         fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
-            formatter.write_str("a base64 string")
+            formatter.write_str(self.expecting)
         }
 
         fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
         where
             E: serde::de::Error,
         {
-            use base64::{Engine as _, engine::general_purpose};
-            let engine = if self.url_safe {
-                &general_purpose::URL_SAFE
-            } else {
-                &general_purpose::STANDARD
-            };
-            let bytes = engine
-                .decode(v)
-                .map_err(|e| E::custom(format!("invalid base64 string: {}", e)))?;
+            let bytes = (self.decode)(v).map_err(E::custom)?;
             self.visitor.visit_bytes(&bytes)
         }
 
@@ -142,5 +268,316 @@ where
         }
     }
 
-    deserializer.deserialize_str(Base64BytesVisitor { url_safe, visitor })
+    deserializer.deserialize_str(StrBytesVisitor {
+        expecting,
+        decode,
+        visitor,
+    })
+}
+
+/// Deserializes bytes from a hexadecimal string "0x1234..." or "1234..."
+pub(crate) fn de_bytes_hex<'de, D, V>(
+    deserializer: D,
+    config: &Config,
+    visitor: V,
+) -> Result<V::Value, D::Error>
+where
+    D: serde::de::Deserializer<'de>,
+    V: Visitor<'de>,
+{
+    de_bytes_str(
+        deserializer,
+        "a hexadecimal string",
+        |v| decode_hex(config, v),
+        visitor,
+    )
+}
+
+/// Decodes a hexadecimal string "0x1234..." or "1234..." into bytes
+///
+/// The prefix is checked against the configured `HexPrefixPolicy` and the digits
+/// against the configured `HexCasePolicy`. When EIP-55 is enabled, 20-byte values are
+/// validated by their checksum instead of the case policy.
+pub(crate) fn decode_hex(config: &Config, value: &str) -> Result<Vec<u8>, String> {
+    let policy = config.hex_prefix_policy;
+    let hex_str = match (policy, value.strip_prefix("0x")) {
+        (HexPrefixPolicy::Lenient, Some(hex_str)) => hex_str,
+        (HexPrefixPolicy::Lenient, None) => value.strip_prefix("0X").unwrap_or(value),
+        (_, None) if value.starts_with("0X") => {
+            return Err(format!(
+                "invalid hex string: uppercase `0X` prefix is not allowed (hex prefix policy {:?})",
+                policy
+            ));
+        }
+        (HexPrefixPolicy::RequirePrefix, Some(hex_str)) => hex_str,
+        (HexPrefixPolicy::RequirePrefix, None) => {
+            return Err(format!(
+                "invalid hex string: missing `0x` prefix (hex prefix policy {:?})",
+                policy
+            ));
+        }
+        (HexPrefixPolicy::ForbidPrefix, Some(_)) => {
+            return Err(format!(
+                "invalid hex string: unexpected `0x` prefix (hex prefix policy {:?})",
+                policy
+            ));
+        }
+        (HexPrefixPolicy::ForbidPrefix, None) => value,
+    };
+
+    if policy != HexPrefixPolicy::Lenient && hex_str.len() % 2 != 0 {
+        return Err(format!(
+            "invalid hex string: odd number of digits (hex prefix policy {:?})",
+            policy
+        ));
+    }
+
+    let bytes = hex::decode(hex_str).map_err(|e| format!("invalid hex string: {}", e))?;
+
+    if config.hex_eip55 && bytes.len() == eip55::ADDRESS_LEN {
+        eip55::verify(hex_str)?;
+        return Ok(bytes);
+    }
+
+    match config.hex_case_policy {
+        HexCasePolicy::Lowercase if hex_str.chars().any(|c| c.is_ascii_uppercase()) => Err(
+            "invalid hex string: uppercase digits are not allowed (hex case policy Lowercase)"
+                .to_string(),
+        ),
+        HexCasePolicy::Uppercase if hex_str.chars().any(|c| c.is_ascii_lowercase()) => Err(
+            "invalid hex string: lowercase digits are not allowed (hex case policy Uppercase)"
+                .to_string(),
+        ),
+        _ => Ok(bytes),
+    }
+}
+
+/// Deserializes bytes from a Base64 string
+///
+/// # Arguments
+///
+/// * `url_safe` - If true, uses URL-safe Base64 decoding, otherwise uses standard Base64
+pub(crate) fn de_bytes_base64<'de, D, V>(
+    deserializer: D,
+    config: &Config,
+    url_safe: bool,
+    visitor: V,
+) -> Result<V::Value, D::Error>
+where
+    D: serde::de::Deserializer<'de>,
+    V: Visitor<'de>,
+{
+    de_bytes_str(
+        deserializer,
+        "a base64 string",
+        |v| decode_base64(config, v, url_safe),
+        visitor,
+    )
+}
+
+/// Decodes a Base64 string into bytes
+///
+/// In strict mode only the configured alphabet and padding are accepted. In lenient
+/// mode padding is optional and the alphabet is detected from the input, falling back
+/// to the configured one when the input contains no alphabet-specific characters.
+pub(crate) fn decode_base64(
+    config: &Config,
+    value: &str,
+    url_safe: bool,
+) -> Result<Vec<u8>, String> {
+    use base64::{
+        Engine as _, alphabet,
+        engine::{DecodePaddingMode, GeneralPurpose, general_purpose},
+    };
+
+    const LENIENT: general_purpose::GeneralPurposeConfig =
+        general_purpose::PAD.with_decode_padding_mode(DecodePaddingMode::Indifferent);
+    const LENIENT_STANDARD: GeneralPurpose = GeneralPurpose::new(&alphabet::STANDARD, LENIENT);
+    const LENIENT_URL_SAFE: GeneralPurpose = GeneralPurpose::new(&alphabet::URL_SAFE, LENIENT);
+
+    let engine = match (config.base64_decode_mode, url_safe, config.base64_padding) {
+        (Base64DecodeMode::Strict, false, true) => &general_purpose::STANDARD,
+        (Base64DecodeMode::Strict, false, false) => &general_purpose::STANDARD_NO_PAD,
+        (Base64DecodeMode::Strict, true, true) => &general_purpose::URL_SAFE,
+        (Base64DecodeMode::Strict, true, false) => &general_purpose::URL_SAFE_NO_PAD,
+        (Base64DecodeMode::Lenient, _, _) => {
+            let has_standard = value.contains(['+', '/']);
+            let has_url_safe = value.contains(['-', '_']);
+            match (has_standard, has_url_safe) {
+                (true, true) => {
+                    return Err(
+                        "invalid base64 string: mixes standard and URL-safe alphabets".to_string(),
+                    );
+                }
+                (true, false) => &LENIENT_STANDARD,
+                (false, true) => &LENIENT_URL_SAFE,
+                (false, false) if url_safe => &LENIENT_URL_SAFE,
+                (false, false) => &LENIENT_STANDARD,
+            }
+        }
+    };
+
+    engine
+        .decode(value)
+        .map_err(|e| format!("invalid base64 string: {}", e))
+}
+
+/// Deserializes bytes from a Base58 string
+///
+/// # Arguments
+///
+/// * `check` - If true, verifies and strips the Base58Check checksum
+pub(crate) fn de_bytes_base58<'de, D, V>(
+    deserializer: D,
+    config: &Config,
+    check: bool,
+    visitor: V,
+) -> Result<V::Value, D::Error>
+where
+    D: serde::de::Deserializer<'de>,
+    V: Visitor<'de>,
+{
+    let expecting = if check {
+        "a base58check string"
+    } else {
+        "a base58 string"
+    };
+    de_bytes_str(
+        deserializer,
+        expecting,
+        |v| decode_base58(config, v, check),
+        visitor,
+    )
+}
+
+/// Decodes a Base58 or Base58Check string into bytes
+pub(crate) fn decode_base58(config: &Config, value: &str, check: bool) -> Result<Vec<u8>, String> {
+    let decoder = bs58::decode(value).with_alphabet(config.base58_alphabet.as_bs58());
+    if check {
+        decoder
+            .with_check(None)
+            .into_vec()
+            .map_err(|e| format!("invalid base58check string: {}", e))
+    } else {
+        decoder
+            .into_vec()
+            .map_err(|e| format!("invalid base58 string: {}", e))
+    }
+}
+
+/// Deserializes bytes from a Bech32 or Bech32m string
+///
+/// # Arguments
+///
+/// * `modified` - If true, expects the Bech32m checksum, otherwise expects Bech32
+pub(crate) fn de_bytes_bech32<'de, D, V>(
+    deserializer: D,
+    config: &Config,
+    modified: bool,
+    visitor: V,
+) -> Result<V::Value, D::Error>
+where
+    D: serde::de::Deserializer<'de>,
+    V: Visitor<'de>,
+{
+    let expecting = if modified {
+        "a bech32m string"
+    } else {
+        "a bech32 string"
+    };
+    de_bytes_str(
+        deserializer,
+        expecting,
+        |v| decode_bech32(config, v, modified),
+        visitor,
+    )
+}
+
+/// Decodes a Bech32 or Bech32m string into bytes
+///
+/// The checksum must be valid and the human-readable part must match the configured one.
+pub(crate) fn decode_bech32(
+    config: &Config,
+    value: &str,
+    modified: bool,
+) -> Result<Vec<u8>, String> {
+    use bech32::{Bech32, Bech32m, primitives::decode::CheckedHrpstring};
+    let checked = if modified {
+        CheckedHrpstring::new::<Bech32m>(value)
+    } else {
+        CheckedHrpstring::new::<Bech32>(value)
+    }
+    .map_err(|e| format!("invalid bech32 string: {}", e))?;
+
+    let hrp = checked.hrp();
+    if !hrp.as_str().eq_ignore_ascii_case(&config.bech32_hrp) {
+        return Err(format!(
+            "unexpected bech32 human-readable part: expected {}, got {}",
+            config.bech32_hrp,
+            hrp.to_lowercase()
+        ));
+    }
+
+    Ok(checked.byte_iter().collect())
+}
+
+/// Deserializes bytes from a Base32 string
+///
+/// # Arguments
+///
+/// * `format` - One of the Base32 formats, selecting the alphabet
+pub(crate) fn de_bytes_base32<'de, D, V>(
+    deserializer: D,
+    config: &Config,
+    format: &BytesFormat,
+    visitor: V,
+) -> Result<V::Value, D::Error>
+where
+    D: serde::de::Deserializer<'de>,
+    V: Visitor<'de>,
+{
+    de_bytes_str(
+        deserializer,
+        "a base32 string",
+        |v| decode_base32(config, v, format),
+        visitor,
+    )
+}
+
+/// Decodes a Base32 string into bytes
+///
+/// Crockford's Base32 is decoded case-insensitively, the other alphabets must
+/// match the configured case.
+pub(crate) fn decode_base32(
+    config: &Config,
+    value: &str,
+    format: &BytesFormat,
+) -> Result<Vec<u8>, String> {
+    base32::decode(config.base32_alphabet(format), value)
+        .ok_or_else(|| "invalid base32 string".to_string())
+}
+
+/// Deserializes bytes from a string in a user-defined encoding
+pub(crate) fn de_bytes_custom<'de, D, V>(
+    deserializer: D,
+    codec: &dyn BytesCodec,
+    visitor: V,
+) -> Result<V::Value, D::Error>
+where
+    D: serde::de::Deserializer<'de>,
+    V: Visitor<'de>,
+{
+    de_bytes_str(
+        deserializer,
+        "a string in a custom bytes format",
+        |v| decode_custom(codec, v),
+        visitor,
+    )
+}
+
+/// Decodes a string in a user-defined encoding into bytes
+pub(crate) fn decode_custom(codec: &dyn BytesCodec, value: &str) -> Result<Vec<u8>, String> {
+    codec
+        .decode(value)
+        .map_err(|e| format!("invalid custom bytes string: {}", e))
 }
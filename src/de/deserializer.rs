@@ -1,26 +1,43 @@
 // Deserializer wrapper for serde_json
 
-use crate::Config;
+use crate::{Config, context::Context, marker};
 use serde::de::Visitor;
 
-use super::{WrapVisitor, bytes};
+use super::{WrapVisitor, bytes, length::LengthVisitor, number};
 
 /// A wrapper around `serde_json::Deserializer` that implements `Deserializer<'de>`
 pub struct Deserializer<'a, D> {
     /// The internal `serde_json::Deserializer`
     pub inner: D,
-    /// Configuration for deserialization
-    pub config: &'a Config,
+    /// Configuration for deserialization at the current location
+    pub ctx: Context<'a>,
 }
 
 impl<'a, D> Deserializer<'a, D> {
     /// Creates a new `Deserializer` from an internal `serde_json::Deserializer` with custom config
     pub fn with_config(inner: D, config: &'a Config) -> Self {
-        Deserializer { inner, config }
+        Deserializer {
+            inner,
+            ctx: Context::new(config),
+        }
+    }
+
+    /// Creates a new `Deserializer` for a value below the document root
+    pub(crate) fn with_context(inner: D, ctx: Context<'a>) -> Self {
+        Deserializer { inner, ctx }
+    }
+
+    /// Wraps a bytes visitor to check the length rule of the current value
+    fn length_visitor<V>(&self, visitor: V) -> LengthVisitor<V> {
+        LengthVisitor {
+            visitor,
+            length: self.ctx.length,
+            path: self.ctx.path.clone(),
+        }
     }
 }
 
-impl<'de, D> serde::de::Deserializer<'de> for Deserializer<'de, D>
+impl<'de, 'a, D> serde::de::Deserializer<'de> for Deserializer<'a, D>
 where
     D: serde::de::Deserializer<'de>,
 {
@@ -32,7 +49,7 @@ where
     {
         self.inner.deserialize_any(WrapVisitor {
             visitor,
-            config: self.config,
+            ctx: self.ctx,
         })
     }
 
@@ -47,84 +64,88 @@ where
     where
         V: Visitor<'de>,
     {
-        self.inner.deserialize_i8(visitor)
+        number::de_integer(self.inner, &self.ctx, visitor, |d, v| d.deserialize_i8(v))
     }
 
     fn deserialize_i16<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        self.inner.deserialize_i16(visitor)
+        number::de_integer(self.inner, &self.ctx, visitor, |d, v| d.deserialize_i16(v))
     }
 
     fn deserialize_i32<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        self.inner.deserialize_i32(visitor)
+        number::de_integer(self.inner, &self.ctx, visitor, |d, v| d.deserialize_i32(v))
     }
 
     fn deserialize_i64<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        self.inner.deserialize_i64(visitor)
+        number::de_integer(self.inner, &self.ctx, visitor, |d, v| d.deserialize_i64(v))
     }
 
     fn deserialize_i128<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        self.inner.deserialize_i128(visitor)
+        number::de_integer(self.inner, &self.ctx, visitor, |d, v| d.deserialize_i128(v))
     }
 
     fn deserialize_u8<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        self.inner.deserialize_u8(visitor)
+        number::de_integer(self.inner, &self.ctx, visitor, |d, v| d.deserialize_u8(v))
     }
 
     fn deserialize_u16<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        self.inner.deserialize_u16(visitor)
+        number::de_integer(self.inner, &self.ctx, visitor, |d, v| d.deserialize_u16(v))
     }
 
     fn deserialize_u32<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        self.inner.deserialize_u32(visitor)
+        number::de_integer(self.inner, &self.ctx, visitor, |d, v| d.deserialize_u32(v))
     }
 
     fn deserialize_u64<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        self.inner.deserialize_u64(visitor)
+        number::de_integer(self.inner, &self.ctx, visitor, |d, v| d.deserialize_u64(v))
     }
 
     fn deserialize_u128<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        self.inner.deserialize_u128(visitor)
+        number::de_integer(self.inner, &self.ctx, visitor, |d, v| d.deserialize_u128(v))
     }
 
     fn deserialize_f32<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        self.inner.deserialize_f32(visitor)
+        number::de_float(self.inner, self.ctx.config, visitor, |d, v| {
+            d.deserialize_f32(v)
+        })
     }
 
     fn deserialize_f64<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        self.inner.deserialize_f64(visitor)
+        number::de_float(self.inner, self.ctx.config, visitor, |d, v| {
+            d.deserialize_f64(v)
+        })
     }
 
     fn deserialize_char<V>(self, visitor: V) -> Result<V::Value, Self::Error>
@@ -152,14 +173,16 @@ where
     where
         V: Visitor<'de>,
     {
-        bytes::de_bytes(self.inner, self.config, visitor)
+        let visitor = self.length_visitor(visitor);
+        bytes::de_bytes(self.inner, self.ctx.config, visitor)
     }
 
     fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        bytes::de_bytes(self.inner, self.config, visitor)
+        let visitor = self.length_visitor(visitor);
+        bytes::de_bytes(self.inner, self.ctx.config, visitor)
     }
 
     fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
@@ -168,7 +191,7 @@ where
     {
         self.inner.deserialize_option(WrapVisitor {
             visitor,
-            config: self.config,
+            ctx: self.ctx,
         })
     }
 
@@ -178,7 +201,7 @@ where
     {
         self.inner.deserialize_unit(WrapVisitor {
             visitor,
-            config: self.config,
+            ctx: self.ctx,
         })
     }
 
@@ -194,7 +217,7 @@ where
             name,
             WrapVisitor {
                 visitor,
-                config: self.config,
+                ctx: self.ctx,
             },
         )
     }
@@ -207,11 +230,25 @@ where
     where
         V: Visitor<'de>,
     {
+        if let Some(canonical) = marker::canonical_config(name) {
+            let visitor = self.length_visitor(marker::NewtypeBytesVisitor(visitor));
+            return bytes::de_bytes_as(
+                self.inner,
+                self.ctx.config,
+                &canonical.bytes_format,
+                visitor,
+            );
+        }
+        if name == marker::BYTES_NAME {
+            let visitor = self.length_visitor(marker::NewtypeBytesVisitor(visitor));
+            return bytes::de_bytes(self.inner, self.ctx.config, visitor);
+        }
+
         self.inner.deserialize_newtype_struct(
             name,
             WrapVisitor {
                 visitor,
-                config: self.config,
+                ctx: self.ctx.newtype(name),
             },
         )
     }
@@ -222,7 +259,7 @@ where
     {
         self.inner.deserialize_seq(WrapVisitor {
             visitor,
-            config: self.config,
+            ctx: self.ctx,
         })
     }
 
@@ -234,7 +271,7 @@ where
             len,
             WrapVisitor {
                 visitor,
-                config: self.config,
+                ctx: self.ctx,
             },
         )
     }
@@ -253,7 +290,7 @@ where
             len,
             WrapVisitor {
                 visitor,
-                config: self.config,
+                ctx: self.ctx,
             },
         )
     }
@@ -264,7 +301,7 @@ where
     {
         self.inner.deserialize_map(WrapVisitor {
             visitor,
-            config: self.config,
+            ctx: self.ctx,
         })
     }
 
@@ -282,7 +319,7 @@ where
             fields,
             WrapVisitor {
                 visitor,
-                config: self.config,
+                ctx: self.ctx,
             },
         )
     }
@@ -291,10 +328,8 @@ where
     where
         V: Visitor<'de>,
     {
-        self.inner.deserialize_identifier(WrapVisitor {
-            visitor,
-            config: self.config,
-        })
+        // Field and variant names are never bytes, so they must not be decoded
+        self.inner.deserialize_identifier(visitor)
     }
 
     fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
@@ -303,7 +338,7 @@ where
     {
         self.inner.deserialize_ignored_any(WrapVisitor {
             visitor,
-            config: self.config,
+            ctx: self.ctx,
         })
     }
 
@@ -321,7 +356,7 @@ where
             variants,
             WrapVisitor {
                 visitor,
-                config: self.config,
+                ctx: self.ctx,
             },
         )
     }
@@ -1,26 +1,96 @@
-use serde::de::{DeserializeSeed, EnumAccess};
+use serde::de::{DeserializeSeed, EnumAccess, VariantAccess, Visitor};
 
-use crate::{Config, de::seed::WrapSeed};
+use crate::{
+    context::Context,
+    de::{WrapVisitor, key::CaptureKey, seed::WrapSeed},
+};
 
 pub struct WrapEnumAccess<'a, A> {
     pub inner: A,
-    pub config: &'a Config,
+    pub ctx: Context<'a>,
 }
 
-impl<'de, A> EnumAccess<'de> for WrapEnumAccess<'de, A>
+impl<'de, 'a, A> EnumAccess<'de> for WrapEnumAccess<'a, A>
 where
     A: EnumAccess<'de>,
 {
     type Error = A::Error;
-    type Variant = A::Variant;
+    type Variant = WrapVariantAccess<'a, A::Variant>;
 
     fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self::Variant), Self::Error>
     where
         V: DeserializeSeed<'de>,
     {
-        self.inner.variant_seed(WrapSeed {
+        let mut variant = None;
+        let seed = CaptureKey {
             seed,
-            config: self.config,
+            key: &mut variant,
+        };
+        let (value, inner) = self.inner.variant_seed(WrapSeed {
+            seed,
+            ctx: self.ctx.clone(),
+        })?;
+
+        let ctx = match variant {
+            Some(variant) => self.ctx.key(&variant),
+            None => self.ctx,
+        };
+        Ok((value, WrapVariantAccess { inner, ctx }))
+    }
+}
+
+pub struct WrapVariantAccess<'a, A> {
+    pub inner: A,
+    pub ctx: Context<'a>,
+}
+
+impl<'de, 'a, A> VariantAccess<'de> for WrapVariantAccess<'a, A>
+where
+    A: VariantAccess<'de>,
+{
+    type Error = A::Error;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        self.inner.unit_variant()
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, Self::Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        self.inner.newtype_variant_seed(WrapSeed {
+            seed,
+            ctx: self.ctx,
         })
     }
+
+    fn tuple_variant<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.inner.tuple_variant(
+            len,
+            WrapVisitor {
+                visitor,
+                ctx: self.ctx,
+            },
+        )
+    }
+
+    fn struct_variant<V>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.inner.struct_variant(
+            fields,
+            WrapVisitor {
+                visitor,
+                ctx: self.ctx,
+            },
+        )
+    }
 }
@@ -1,9 +1,12 @@
 use serde::{Deserialize, de::DeserializeOwned};
 use serde_json::{Result, de::Read};
 
-use crate::{Config, de::Deserializer};
+use crate::{
+    Config,
+    de::{Deserializer, buffered},
+};
 
-fn from_trait<'de, R, T>(read: R, config: &'de Config) -> Result<T>
+fn from_trait<'de, R, T>(read: R, config: &Config) -> Result<T>
 where
     R: Read<'de>,
     T: Deserialize<'de>,
@@ -11,7 +14,9 @@ where
     let mut serde_json_de = serde_json::Deserializer::new(read);
     let de = Deserializer::with_config(&mut serde_json_de, config);
 
-    let value = serde::de::Deserialize::deserialize(de)?;
+    // Buffered content is decoded without the wrapper, `Bytes` looks up the rules recorded
+    // during the call
+    let value = buffered::with_call(config, || serde::de::Deserialize::deserialize(de))?;
 
     serde_json_de.end()?;
 
@@ -26,14 +31,14 @@ where
     from_trait(serde_json::de::IoRead::new(rdr), config)
 }
 
-pub fn from_slice<'a, T>(v: &'a [u8], config: &'a Config) -> Result<T>
+pub fn from_slice<'a, T>(v: &'a [u8], config: &Config) -> Result<T>
 where
     T: Deserialize<'a>,
 {
     from_trait(serde_json::de::SliceRead::new(v), config)
 }
 
-pub fn from_str<'a, T>(s: &'a str, config: &'a Config) -> Result<T>
+pub fn from_str<'a, T>(s: &'a str, config: &Config) -> Result<T>
 where
     T: Deserialize<'a>,
 {
@@ -46,7 +51,7 @@ where
 {
     let de = Deserializer::with_config(value, config);
 
-    let value = serde::de::Deserialize::deserialize(de)?;
+    let value = buffered::with_call(config, || serde::de::Deserialize::deserialize(de))?;
 
     Ok(value)
 }
@@ -56,6 +61,10 @@ mod tests {
     use serde_json::json;
 
     use super::*;
+    use crate::{
+        Base58, Base64, Base64DecodeMode, Base64UrlSafe, Bytes, BytesCodec, Hex, HexCasePolicy,
+        HexPrefixPolicy, NonFiniteFloatPolicy,
+    };
 
     #[test]
     fn test_from_str_hex_without_prefix_to_vec_u8() {
@@ -244,4 +253,1278 @@ mod tests {
             UntaggedBytes::Other(_) => panic!("unexpected untagged variant"),
         }
     }
+
+    #[test]
+    fn test_from_str_hex_eip55_checksum() {
+        let config = Config::default()
+            .set_bytes_hex()
+            .enable_hex_prefix()
+            .enable_hex_eip55();
+
+        #[derive(Deserialize, Debug)]
+        struct TestStruct {
+            #[serde(with = "serde_bytes")]
+            address: Vec<u8>,
+        }
+
+        let expected = hex::decode("5aaeb6053f3e94c9b9a09f33669435e7ef1beaed").unwrap();
+
+        let json = r#"{"address":"0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"}"#;
+        let result: TestStruct = from_str(json, &config).unwrap();
+        assert_eq!(result.address, expected);
+
+        let json = r#"{"address":"0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed"}"#;
+        let result: TestStruct = from_str(json, &config).unwrap();
+        assert_eq!(result.address, expected);
+
+        let json = r#"{"address":"0x5AAEB6053F3E94C9B9A09F33669435E7EF1BEAED"}"#;
+        let result: TestStruct = from_str(json, &config).unwrap();
+        assert_eq!(result.address, expected);
+
+        let json = r#"{"address":"0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD"}"#;
+        let err = from_str::<TestStruct>(json, &config).unwrap_err();
+        assert!(err.to_string().contains("invalid EIP-55 checksum"));
+
+        let config = config.disable_hex_eip55();
+        let result: TestStruct = from_str(json, &config).unwrap();
+        assert_eq!(result.address, expected);
+    }
+
+    #[test]
+    fn test_from_str_base58() {
+        #[derive(Deserialize, Debug)]
+        struct TestStruct {
+            #[serde(with = "serde_bytes")]
+            data: Vec<u8>,
+        }
+
+        let config = Config::default().set_bytes_base58();
+        let json = r#"{"data":"2NEpo7TZRRrLZSi2U"}"#;
+        let result: TestStruct = from_str(json, &config).unwrap();
+        assert_eq!(result.data, b"Hello World!");
+
+        let json = r#"{"data":"0OIl"}"#;
+        let err = from_str::<TestStruct>(json, &config).unwrap_err();
+        assert!(err.to_string().contains("invalid base58 string"));
+
+        let config = Config::default().set_bytes_base58_check();
+        let json = r#"{"data":"16UwLL9Risc3QfPqBUvKofHmBQ7wMtjvM"}"#;
+        let result: TestStruct = from_str(json, &config).unwrap();
+        assert_eq!(
+            result.data,
+            hex::decode("00010966776006953d5567439e5e39f86a0d273bee").unwrap()
+        );
+
+        let json = r#"{"data":"16UwLL9Risc3QfPqBUvKofHmBQ7wMtjvN"}"#;
+        let err = from_str::<TestStruct>(json, &config).unwrap_err();
+        assert!(err.to_string().contains("invalid base58check string"));
+    }
+
+    #[test]
+    fn test_from_str_bech32() {
+        #[derive(Deserialize, Debug)]
+        struct TestStruct {
+            #[serde(with = "serde_bytes")]
+            address: Vec<u8>,
+        }
+
+        let expected: Vec<u8> = (0u8..20).collect();
+
+        let config = Config::default().set_bytes_bech32("cosmos");
+        let json = r#"{"address":"cosmos1qqqsyqcyq5rqwzqfpg9scrgwpugpzysnrk363e"}"#;
+        let result: TestStruct = from_str(json, &config).unwrap();
+        assert_eq!(result.address, expected);
+
+        let json = r#"{"address":"cosmos1qqqsyqcyq5rqwzqfpg9scrgwpugpzysnrk363f"}"#;
+        let err = from_str::<TestStruct>(json, &config).unwrap_err();
+        assert!(err.to_string().contains("invalid bech32 string"));
+
+        let json = r#"{"address":"cosmos1qqqsyqcyq5rqwzqfpg9scrgwpugpzysnk2pk5m"}"#;
+        assert!(from_str::<TestStruct>(json, &config).is_err());
+
+        let config = Config::default().set_bytes_bech32m("cosmos");
+        let result: TestStruct = from_str(json, &config).unwrap();
+        assert_eq!(result.address, expected);
+
+        let config = Config::default().set_bytes_bech32m("osmo");
+        let err = from_str::<TestStruct>(json, &config).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("unexpected bech32 human-readable part: expected osmo, got cosmos")
+        );
+    }
+
+    #[test]
+    fn test_from_str_base32() {
+        #[derive(Deserialize, Debug)]
+        struct TestStruct {
+            #[serde(with = "serde_bytes")]
+            data: Vec<u8>,
+        }
+
+        let test_cases = vec![
+            (
+                Config::default().set_bytes_base32(),
+                r#"{"data":"MZXW6YTBOI======"}"#,
+            ),
+            (
+                Config::default().set_bytes_base32(),
+                r#"{"data":"MZXW6YTBOI"}"#,
+            ),
+            (
+                Config::default()
+                    .set_bytes_base32()
+                    .enable_base32_lowercase(),
+                r#"{"data":"mzxw6ytboi======"}"#,
+            ),
+            (
+                Config::default().set_bytes_base32_hex(),
+                r#"{"data":"CPNMUOJ1E8======"}"#,
+            ),
+            (
+                Config::default().set_bytes_base32_crockford(),
+                r#"{"data":"CSQPYRK1E8"}"#,
+            ),
+            (
+                Config::default().set_bytes_base32_crockford(),
+                r#"{"data":"csqpyrk1e8"}"#,
+            ),
+            (
+                Config::default().set_bytes_base32_crockford(),
+                r#"{"data":"CSQPYRKlE8"}"#,
+            ),
+            (
+                Config::default().set_bytes_z_base32(),
+                r#"{"data":"c3zs6aubqe"}"#,
+            ),
+        ];
+
+        for (config, json) in test_cases {
+            let result: TestStruct = from_str(json, &config).unwrap();
+            assert_eq!(result.data, b"foobar");
+        }
+
+        let config = Config::default().set_bytes_base32();
+        let json = r#"{"data":"mzxw6ytboi======"}"#;
+        let err = from_str::<TestStruct>(json, &config).unwrap_err();
+        assert!(err.to_string().contains("invalid base32 string"));
+
+        let config = Config::default()
+            .set_bytes_base32()
+            .disable_base32_padding();
+        let json = r#"{"data":"MZXW6YTBOI======"}"#;
+        assert!(from_str::<TestStruct>(json, &config).is_err());
+    }
+
+    #[test]
+    fn test_from_str_base64_decode_mode() {
+        #[derive(Deserialize, Debug)]
+        struct TestStruct {
+            #[serde(with = "serde_bytes")]
+            data: Vec<u8>,
+        }
+
+        let expected = vec![1u8, 2u8, 3u8, 255u8];
+
+        let config = Config::default().set_bytes_base64();
+        let result: TestStruct = from_str(r#"{"data":"AQID/w=="}"#, &config).unwrap();
+        assert_eq!(result.data, expected);
+        let err = from_str::<TestStruct>(r#"{"data":"AQID/w"}"#, &config).unwrap_err();
+        assert!(err.to_string().contains("invalid base64 string"));
+        assert!(from_str::<TestStruct>(r#"{"data":"AQID_w=="}"#, &config).is_err());
+
+        let config = Config::default()
+            .set_bytes_base64()
+            .disable_base64_padding();
+        let result: TestStruct = from_str(r#"{"data":"AQID/w"}"#, &config).unwrap();
+        assert_eq!(result.data, expected);
+        assert!(from_str::<TestStruct>(r#"{"data":"AQID/w=="}"#, &config).is_err());
+
+        let config = Config::default()
+            .set_bytes_base64()
+            .set_base64_decode_mode(Base64DecodeMode::Lenient);
+        for json in [
+            r#"{"data":"AQID/w=="}"#,
+            r#"{"data":"AQID/w"}"#,
+            r#"{"data":"AQID_w=="}"#,
+            r#"{"data":"AQID_w"}"#,
+        ] {
+            let result: TestStruct = from_str(json, &config).unwrap();
+            assert_eq!(result.data, expected);
+        }
+
+        let err = from_str::<TestStruct>(r#"{"data":"+QID_w"}"#, &config).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("mixes standard and URL-safe alphabets")
+        );
+    }
+
+    #[test]
+    fn test_from_str_hex_prefix_policy() {
+        #[derive(Deserialize, Debug)]
+        struct TestStruct {
+            #[serde(with = "serde_bytes")]
+            data: Vec<u8>,
+        }
+
+        let config = Config::default().set_bytes_hex();
+        for json in [
+            r#"{"data":"0x0000ff"}"#,
+            r#"{"data":"0X0000ff"}"#,
+            r#"{"data":"0000ff"}"#,
+        ] {
+            let result: TestStruct = from_str(json, &config).unwrap();
+            assert_eq!(result.data, vec![0, 0, 255]);
+        }
+
+        let config = Config::default()
+            .set_bytes_hex()
+            .set_hex_prefix_policy(HexPrefixPolicy::RequirePrefix);
+        let result: TestStruct = from_str(r#"{"data":"0x0000ff"}"#, &config).unwrap();
+        assert_eq!(result.data, vec![0, 0, 255]);
+
+        let test_cases = [
+            (
+                r#"{"data":"0000ff"}"#,
+                "missing `0x` prefix (hex prefix policy RequirePrefix)",
+            ),
+            (
+                r#"{"data":"0X0000ff"}"#,
+                "uppercase `0X` prefix is not allowed (hex prefix policy RequirePrefix)",
+            ),
+            (
+                r#"{"data":"0x000ff"}"#,
+                "odd number of digits (hex prefix policy RequirePrefix)",
+            ),
+        ];
+        for (json, message) in test_cases {
+            let err = from_str::<TestStruct>(json, &config).unwrap_err();
+            assert!(err.to_string().contains(message), "{}", err);
+        }
+
+        let config = Config::default()
+            .set_bytes_hex()
+            .set_hex_prefix_policy(HexPrefixPolicy::ForbidPrefix);
+        let result: TestStruct = from_str(r#"{"data":"0000ff"}"#, &config).unwrap();
+        assert_eq!(result.data, vec![0, 0, 255]);
+
+        let err = from_str::<TestStruct>(r#"{"data":"0x0000ff"}"#, &config).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("unexpected `0x` prefix (hex prefix policy ForbidPrefix)")
+        );
+    }
+
+    #[test]
+    fn test_from_str_hex_case_policy() {
+        #[derive(Deserialize, Debug)]
+        struct TestStruct {
+            #[serde(with = "serde_bytes")]
+            data: Vec<u8>,
+        }
+
+        let expected = vec![0xde, 0xad, 0xbe, 0xef];
+
+        let config = Config::default().set_bytes_hex();
+        for json in [
+            r#"{"data":"0xdeadbeef"}"#,
+            r#"{"data":"0xDEADBEEF"}"#,
+            r#"{"data":"0xDeadBeef"}"#,
+        ] {
+            let result: TestStruct = from_str(json, &config).unwrap();
+            assert_eq!(result.data, expected);
+        }
+
+        let config = Config::default()
+            .set_bytes_hex()
+            .set_hex_case_policy(HexCasePolicy::Uppercase);
+        let result: TestStruct = from_str(r#"{"data":"0xDEADBEEF"}"#, &config).unwrap();
+        assert_eq!(result.data, expected);
+        let err = from_str::<TestStruct>(r#"{"data":"0xDeadBeef"}"#, &config).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("lowercase digits are not allowed (hex case policy Uppercase)")
+        );
+
+        let config = Config::default()
+            .set_bytes_hex()
+            .set_hex_case_policy(HexCasePolicy::Lowercase);
+        let result: TestStruct = from_str(r#"{"data":"0xdeadbeef"}"#, &config).unwrap();
+        assert_eq!(result.data, expected);
+        let err = from_str::<TestStruct>(r#"{"data":"0xDEADBEEF"}"#, &config).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("uppercase digits are not allowed (hex case policy Lowercase)")
+        );
+    }
+
+    #[test]
+    fn test_from_str_integer_quantity() {
+        #[derive(Deserialize, Debug)]
+        struct TestStruct {
+            number: u64,
+            gas: u32,
+            total_difficulty: u128,
+            offset: i64,
+        }
+
+        let config = Config::default().set_integer_quantity();
+
+        let json = r#"{"number":"0x1b4","gas":"0x0","total_difficulty":"0xffffffffffffffffffffffffffffffff","offset":16}"#;
+        let result: TestStruct = from_str(json, &config).unwrap();
+        assert_eq!(result.number, 436);
+        assert_eq!(result.gas, 0);
+        assert_eq!(result.total_difficulty, u128::MAX);
+        assert_eq!(result.offset, 16);
+
+        let result: TestStruct = from_value(
+            json!({
+                "number": "0x1B4",
+                "gas": 21000,
+                "total_difficulty": "0x10",
+                "offset": "0x0",
+            }),
+            &config,
+        )
+        .unwrap();
+        assert_eq!(result.number, 436);
+        assert_eq!(result.gas, 21000);
+        assert_eq!(result.total_difficulty, 16);
+        assert_eq!(result.offset, 0);
+
+        let err = from_str::<u64>(r#""1b4""#, &config).unwrap_err();
+        assert!(err.to_string().contains("missing `0x` prefix"));
+        assert!(from_str::<u64>(r#""0x""#, &config).is_err());
+        assert!(from_str::<u64>(r#""0x+1""#, &config).is_err());
+        assert!(from_str::<u8>(r#""0x100""#, &config).is_err());
+
+        // Object keys are strings, decimal keys are accepted as well
+        let result: std::collections::BTreeMap<u64, u64> =
+            from_str(r#"{"1":"0x2","0x1b4":"0x3"}"#, &config).unwrap();
+        assert_eq!(result, [(1, 2), (436, 3)].into());
+        let result: std::collections::BTreeMap<i64, u64> =
+            from_str(r#"{"-1":"0x2"}"#, &config).unwrap();
+        assert_eq!(result, [(-1, 2)].into());
+        let config = config.add_path_rule("$.other", Config::default());
+        let result: std::collections::BTreeMap<u64, u64> =
+            from_str(r#"{"1":"0x2"}"#, &config).unwrap();
+        assert_eq!(result, [(1, 2)].into());
+        assert!(from_str::<std::collections::BTreeMap<u64, u64>>(r#"{"1":"2"}"#, &config).is_err());
+
+        let config = Config::default();
+        assert!(from_str::<u64>(r#""0x1b4""#, &config).is_err());
+    }
+
+    #[test]
+    fn test_from_str_integer_string() {
+        #[derive(Deserialize, Debug)]
+        struct TestStruct {
+            id: u64,
+            small_id: u64,
+            delta: i64,
+            balance: u128,
+            count: u32,
+        }
+
+        let config = Config::default().set_integer_safe_string();
+
+        let json = r#"{"id":"9007199254740993","small_id":42,"delta":"-9007199254740993","balance":"340282366920938463463374607431768211455","count":7}"#;
+        let result: TestStruct = from_str(json, &config).unwrap();
+        assert_eq!(result.id, 9007199254740993);
+        assert_eq!(result.small_id, 42);
+        assert_eq!(result.delta, -9007199254740993);
+        assert_eq!(result.balance, u128::MAX);
+        assert_eq!(result.count, 7);
+
+        let config = Config::default().set_integer_string();
+        let result: i128 =
+            from_str(r#""-170141183460469231731687303715884105728""#, &config).unwrap();
+        assert_eq!(result, i128::MIN);
+
+        let err = from_str::<u64>(r#""12a""#, &config).unwrap_err();
+        assert!(err.to_string().contains("invalid integer string"));
+        assert!(from_str::<u64>(r#""-1""#, &config).is_err());
+        assert!(from_str::<u64>(r#""+7""#, &config).is_err());
+        assert!(from_str::<u64>(r#""""#, &config).is_err());
+    }
+
+    #[test]
+    fn test_from_str_non_finite_float() {
+        #[derive(Deserialize, Debug)]
+        struct TestStruct {
+            nan: f64,
+            inf: f32,
+            neg_inf: f64,
+            value: f64,
+        }
+
+        let json = r#"{"nan":"NaN","inf":"Infinity","neg_inf":"-Infinity","value":1.5}"#;
+
+        let config = Config::default().set_non_finite_float_policy(NonFiniteFloatPolicy::String);
+        let result: TestStruct = from_str(json, &config).unwrap();
+        assert!(result.nan.is_nan());
+        assert_eq!(result.inf, f32::INFINITY);
+        assert_eq!(result.neg_inf, f64::NEG_INFINITY);
+        assert_eq!(result.value, 1.5);
+
+        assert!(from_str::<f64>(r#""infinity""#, &config).is_err());
+
+        let config = Config::default();
+        assert!(from_str::<TestStruct>(json, &config).is_err());
+    }
+
+    #[test]
+    fn test_from_str_path_rules() {
+        #[derive(Deserialize, Debug)]
+        struct Input {
+            #[serde(with = "serde_bytes")]
+            hash: Vec<u8>,
+        }
+
+        #[derive(Deserialize, Debug)]
+        struct Tx {
+            #[serde(with = "serde_bytes")]
+            signature: Vec<u8>,
+            #[serde(with = "serde_bytes")]
+            data: Vec<u8>,
+            inputs: Vec<Input>,
+            meta: std::collections::HashMap<String, Input>,
+        }
+
+        #[derive(Deserialize, Debug)]
+        struct TestStruct {
+            tx: Tx,
+            #[serde(with = "serde_bytes")]
+            extra: Vec<u8>,
+        }
+
+        let config = Config::default()
+            .set_bytes_hex()
+            .add_path_rule("$.tx.signature", Config::default().set_bytes_base64())
+            .add_path_rule("$.tx.inputs[0]", Config::default())
+            .add_path_rule(
+                "$.tx.*[1].hash",
+                Config::default()
+                    .set_bytes_hex()
+                    .set_hex_prefix_policy(HexPrefixPolicy::RequirePrefix),
+            )
+            .add_path_rule("$.tx.meta.*", Config::default().set_bytes_base58());
+
+        let json = r#"{"tx":{"signature":"//4=","data":"0102","inputs":[{"hash":[10]},{"hash":"0x0b"}],"meta":{"origin":{"hash":"3x"}}},"extra":"03"}"#;
+        let result: TestStruct = from_str(json, &config).unwrap();
+        assert_eq!(result.tx.signature, vec![0xff, 0xfe]);
+        assert_eq!(result.tx.data, vec![0x01, 0x02]);
+        assert_eq!(result.tx.inputs[0].hash, vec![0x0a]);
+        assert_eq!(result.tx.inputs[1].hash, vec![0x0b]);
+        assert_eq!(result.tx.meta["origin"].hash, vec![0xab]);
+        assert_eq!(result.extra, vec![0x03]);
+
+        let json = r#"{"tx":{"signature":"//4=","data":"0102","inputs":[{"hash":[10]},{"hash":"0b"}],"meta":{}},"extra":"03"}"#;
+        let err = from_str::<TestStruct>(json, &config).unwrap_err();
+        assert!(err.to_string().contains("missing `0x` prefix"));
+
+        #[derive(Deserialize, Debug)]
+        enum Message {
+            Payload {
+                #[serde(with = "serde_bytes")]
+                data: Vec<u8>,
+            },
+        }
+
+        let config = Config::default()
+            .set_bytes_hex()
+            .add_path_rule("$.Payload.data", Config::default().set_bytes_base64());
+        let Message::Payload { data } =
+            from_value(json!({"Payload": {"data": "/w=="}}), &config).unwrap();
+        assert_eq!(data, vec![0xff]);
+    }
+
+    #[test]
+    fn test_from_str_type_rules() {
+        #[derive(Deserialize, Debug)]
+        struct Address(#[serde(with = "serde_bytes")] Vec<u8>);
+
+        #[derive(Deserialize, Debug)]
+        struct Signature(#[serde(with = "serde_bytes")] Vec<u8>);
+
+        #[derive(Deserialize, Debug)]
+        struct BlockNumber(u64);
+
+        #[derive(Deserialize, Debug)]
+        struct TestStruct {
+            from: Address,
+            signature: Signature,
+            number: BlockNumber,
+            nonce: u64,
+            #[serde(with = "serde_bytes")]
+            data: Vec<u8>,
+        }
+
+        let config = Config::default()
+            .set_bytes_hex()
+            .add_type_rule(
+                "Address",
+                Config::default()
+                    .set_bytes_hex()
+                    .set_hex_prefix_policy(HexPrefixPolicy::RequirePrefix)
+                    .enable_hex_eip55(),
+            )
+            .add_type_rule("Signature", Config::default().set_bytes_base64())
+            .add_type_rule("BlockNumber", Config::default().set_integer_quantity());
+
+        let json = r#"{"from":"0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed","signature":"//4=","number":"0x1b4","nonce":7,"data":"0102"}"#;
+        let result: TestStruct = from_str(json, &config).unwrap();
+        assert_eq!(
+            result.from.0,
+            hex::decode("5aaeb6053f3e94c9b9a09f33669435e7ef1beaed").unwrap()
+        );
+        assert_eq!(result.signature.0, vec![0xff, 0xfe]);
+        assert_eq!(result.number.0, 436);
+        assert_eq!(result.nonce, 7);
+        assert_eq!(result.data, vec![0x01, 0x02]);
+
+        let json = r#"{"from":"0x5aaeb6053F3E94C9b9A09f33669435E7Ef1BeAed","signature":"//4=","number":"0x1b4","nonce":7,"data":"0102"}"#;
+        let err = from_str::<TestStruct>(json, &config).unwrap_err();
+        assert!(err.to_string().contains("invalid EIP-55 checksum"));
+
+        let json = r#"{"from":"0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed","signature":"//4=","number":"0x1b4","nonce":"0x7","data":"0102"}"#;
+        assert!(from_str::<TestStruct>(json, &config).is_err());
+    }
+
+    #[test]
+    fn test_from_str_custom_codec() {
+        #[derive(Debug)]
+        struct ChecksumError;
+
+        impl std::fmt::Display for ChecksumError {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str("checksum mismatch")
+            }
+        }
+
+        impl std::error::Error for ChecksumError {}
+
+        struct ChecksumHex;
+
+        impl BytesCodec for ChecksumHex {
+            fn encode(&self, _value: &[u8]) -> String {
+                unreachable!()
+            }
+
+            fn decode(
+                &self,
+                value: &str,
+            ) -> std::result::Result<Vec<u8>, Box<dyn std::error::Error + Send + Sync>>
+            {
+                let (data, checksum) = value.split_once(':').ok_or(ChecksumError)?;
+                let data = hex::decode(data)?;
+                let checksum = u8::from_str_radix(checksum, 16)?;
+                if data.iter().fold(0u8, |acc, b| acc ^ b) != checksum {
+                    return Err(Box::new(ChecksumError));
+                }
+                Ok(data)
+            }
+        }
+
+        #[derive(Deserialize, Debug)]
+        struct TestStruct {
+            #[serde(with = "serde_bytes")]
+            data: Vec<u8>,
+            items: Vec<serde_bytes::ByteBuf>,
+        }
+
+        let config = Config::default().set_bytes_custom(std::sync::Arc::new(ChecksumHex));
+
+        let json = r#"{"data":"010203:00","items":["ff:ff"]}"#;
+        let result: TestStruct = from_str(json, &config).unwrap();
+        assert_eq!(result.data, vec![0x01, 0x02, 0x03]);
+        assert_eq!(result.items[0].as_ref(), &[0xff]);
+
+        let json = r#"{"data":"010203:01","items":[]}"#;
+        let err = from_str::<TestStruct>(json, &config).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("invalid custom bytes string: checksum mismatch")
+        );
+    }
+
+    #[test]
+    fn test_from_str_marker_types() {
+        #[derive(Deserialize, Debug)]
+        struct TestStruct {
+            hash: Hex<Vec<u8>>,
+            signature: Base64<[u8; 2]>,
+            key: Base58<Vec<u8>>,
+            token: Option<Base64UrlSafe<Vec<u8>>>,
+            #[serde(with = "serde_bytes")]
+            data: Vec<u8>,
+        }
+
+        let config = Config::default()
+            .set_bytes_base32()
+            .set_hex_prefix_policy(HexPrefixPolicy::ForbidPrefix)
+            .set_base64_decode_mode(Base64DecodeMode::Lenient);
+
+        let json =
+            r#"{"hash":"DEAD","signature":"//4","key":"12","token":"__4","data":"AEBA===="}"#;
+        let result: TestStruct = from_str(json, &config).unwrap();
+        assert_eq!(result.hash.0, vec![0xde, 0xad]);
+        assert_eq!(result.signature.0, [0xff, 0xfe]);
+        assert_eq!(result.key.0, vec![0x00, 0x01]);
+        assert_eq!(result.token.unwrap().0, vec![0xff, 0xfe]);
+        assert_eq!(result.data, vec![0x01, 0x02]);
+
+        let json =
+            r#"{"hash":"0xdead","signature":"//4=","key":"12","token":null,"data":"AEBA===="}"#;
+        let err = from_str::<TestStruct>(json, &config).unwrap_err();
+        assert!(err.to_string().contains("unexpected `0x` prefix"));
+
+        let json =
+            r#"{"hash":"dead","signature":"//79","key":"12","token":null,"data":"AEBA===="}"#;
+        let err = from_str::<TestStruct>(json, &config).unwrap_err();
+        assert!(err.to_string().contains("invalid length 3"));
+
+        let json =
+            r#"{"hash":"0xdead","signature":[255,254],"key":"12","token":"__4=","data":[1,2]}"#;
+        let result: TestStruct = serde_json::from_str(json).unwrap();
+        assert_eq!(result.hash.0, vec![0xde, 0xad]);
+        assert_eq!(result.signature.0, [0xff, 0xfe]);
+        assert_eq!(result.key.0, vec![0x00, 0x01]);
+        assert_eq!(result.token.unwrap().0, vec![0xff, 0xfe]);
+    }
+
+    #[test]
+    fn test_from_str_with_modules() {
+        #[derive(Deserialize, Debug)]
+        struct TestStruct {
+            #[serde(with = "crate::as_hex")]
+            hex: Vec<u8>,
+            #[serde(with = "crate::as_hex_prefixed")]
+            hash: [u8; 2],
+            #[serde(with = "crate::as_eip55")]
+            address: Vec<u8>,
+            #[serde(with = "crate::as_base64")]
+            signature: Vec<u8>,
+            #[serde(with = "crate::as_base64_url_safe::option")]
+            token: Option<Vec<u8>>,
+            #[serde(with = "crate::as_base58::option", default)]
+            key: Option<Vec<u8>>,
+            #[serde(with = "crate::as_hex_prefixed::vec")]
+            proofs: Vec<[u8; 1]>,
+        }
+
+        let json = r#"{"hex":"0xdead","hash":"beef","address":"0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed","signature":"//4=","token":"__4=","key":null,"proofs":["0x01","0x02"]}"#;
+        let config = Config::default().set_bytes_base32();
+        for result in [
+            serde_json::from_str::<TestStruct>(json).unwrap(),
+            from_str::<TestStruct>(json, &config).unwrap(),
+        ] {
+            assert_eq!(result.hex, vec![0xde, 0xad]);
+            assert_eq!(result.hash, [0xbe, 0xef]);
+            assert_eq!(
+                result.address,
+                hex::decode("5aaeb6053f3e94c9b9a09f33669435e7ef1beaed").unwrap()
+            );
+            assert_eq!(result.signature, vec![0xff, 0xfe]);
+            assert_eq!(result.token, Some(vec![0xff, 0xfe]));
+            assert_eq!(result.key, None);
+            assert_eq!(result.proofs, vec![[0x01], [0x02]]);
+        }
+
+        let json =
+            r#"{"hex":"","hash":"0xbeef00","address":"","signature":"","token":null,"proofs":[]}"#;
+        let err = serde_json::from_str::<TestStruct>(json).unwrap_err();
+        assert!(err.to_string().contains("invalid length 3"));
+
+        let json = r#"{"hex":"","hash":"0xbeef","address":"0x5aaeb6053F3E94C9b9A09f33669435E7Ef1BeAed","signature":"","token":null,"proofs":[]}"#;
+        let err = serde_json::from_str::<TestStruct>(json).unwrap_err();
+        assert!(err.to_string().contains("invalid EIP-55 checksum"));
+    }
+
+    #[test]
+    fn test_from_str_shared_config() {
+        #[derive(Deserialize, Debug)]
+        struct TestStruct<'a> {
+            name: &'a str,
+            #[serde(with = "serde_bytes")]
+            data: Vec<u8>,
+        }
+
+        let json = r#"{"name":"block","data":"0x0102"}"#.to_string();
+
+        // The config may be a temporary, only the input has to outlive borrowed values
+        let result: TestStruct = from_str(&json, &Config::default().set_bytes_hex()).unwrap();
+        assert_eq!(result.name, "block");
+        assert_eq!(result.data, vec![0x01, 0x02]);
+
+        static CONFIG: std::sync::LazyLock<Config> =
+            std::sync::LazyLock::new(|| Config::default().set_bytes_hex());
+        let result: TestStruct = from_slice(json.as_bytes(), &CONFIG).unwrap();
+        assert_eq!(result.data, vec![0x01, 0x02]);
+
+        let config = std::sync::Arc::new(Config::default().set_bytes_hex());
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let config = config.clone();
+                let json = json.clone();
+                std::thread::spawn(move || {
+                    let result: TestStruct = from_str(&json, &config).unwrap();
+                    result.data
+                })
+            })
+            .collect();
+        for handle in handles {
+            assert_eq!(handle.join().unwrap(), vec![0x01, 0x02]);
+        }
+    }
+
+    #[test]
+    fn test_from_str_scoped_config() {
+        #[derive(Deserialize, Debug)]
+        struct TestStruct {
+            #[serde(with = "serde_bytes")]
+            data: Vec<u8>,
+        }
+
+        let config = Config::default().set_bytes_base64();
+        let result: TestStruct = crate::with_config(&config, || {
+            crate::global::from_str(r#"{"data":"AQI="}"#).unwrap()
+        });
+        assert_eq!(result.data, vec![0x01, 0x02]);
+
+        let config = Config::default().set_bytes_hex();
+        let result: TestStruct = crate::with_config(&config, || {
+            crate::global::from_value(json!({"data": "0x0102"})).unwrap()
+        });
+        assert_eq!(result.data, vec![0x01, 0x02]);
+    }
+
+    #[test]
+    fn test_from_str_config() {
+        #[derive(Deserialize, Debug)]
+        struct TestStruct {
+            #[serde(with = "serde_bytes")]
+            data: Vec<u8>,
+            amount: u64,
+        }
+
+        let config: Config = serde_json::from_str(
+            r#"{"bytes_format":"base64_url_safe","base64_padding":false,"integer_format":"string"}"#,
+        )
+        .unwrap();
+        let result: TestStruct = from_str(r#"{"data":"__4","amount":"42"}"#, &config).unwrap();
+        assert_eq!(result.data, vec![0xff, 0xfe]);
+        assert_eq!(result.amount, 42);
+
+        assert!(serde_json::from_str::<Config>(r#"{"bytes_format":"custom"}"#).is_err());
+        assert!(serde_json::from_str::<Config>(r#"{"bytes_format":"hexx"}"#).is_err());
+
+        let err = serde_json::from_str::<Config>(r#"{"bytes_fromat":"hex"}"#).unwrap_err();
+        assert!(err.to_string().starts_with("unknown field `bytes_fromat`"));
+    }
+
+    #[test]
+    fn test_from_str_config_from_env() {
+        #[derive(Deserialize, Debug)]
+        struct TestStruct {
+            #[serde(with = "serde_bytes")]
+            data: Vec<u8>,
+        }
+
+        use crate::BytesFormat;
+
+        let mut vars = std::collections::HashMap::from([
+            ("APP_BYTES_FORMAT", "hex"),
+            ("APP_HEX_PREFIX_POLICY", "require_prefix"),
+            ("APP_HEX_EIP55", "true"),
+            ("APP_BYTES_DECODE_FORMATS", "hex, base58,"),
+        ]);
+        let config =
+            Config::from_env_with("APP", |name| vars.get(name).map(|v| v.to_string())).unwrap();
+        assert_eq!(
+            config.bytes_decode_formats(),
+            [BytesFormat::Hex, BytesFormat::Base58]
+        );
+        let result: TestStruct = from_str(r#"{"data":"0x0102"}"#, &config).unwrap();
+        assert_eq!(result.data, vec![0x01, 0x02]);
+        assert!(from_str::<TestStruct>(r#"{"data":"0102"}"#, &config).is_err());
+
+        vars.insert("APP_HEX_EIP55", "yes");
+        let err =
+            Config::from_env_with("APP", |name| vars.get(name).map(|v| v.to_string())).unwrap_err();
+        assert!(err.to_string().contains("APP_HEX_EIP55"));
+
+        vars.insert("APP_BYTES_FORMAT", "hex58");
+        assert!(
+            Config::from_env_with("APP", |name| vars.get(name).map(|v| v.to_string())).is_err()
+        );
+
+        // Unset variables keep their default
+        let config = Config::from_env_with("APP", |_| None).unwrap();
+        assert_eq!(config.bytes_format(), &BytesFormat::Default);
+
+        // Every option that can be loaded has a variable
+        let read = std::cell::RefCell::new(Vec::new());
+        Config::from_env_with("APP", |name| {
+            read.borrow_mut().push(name.to_string());
+            None
+        })
+        .unwrap();
+        let mut read = read.into_inner();
+        read.sort();
+        let options = serde_json::to_value(Config::default()).unwrap();
+        let mut expected: Vec<_> = (options.as_object().unwrap().keys())
+            .map(|option| format!("APP_{}", option.to_uppercase()))
+            .collect();
+        expected.sort();
+        assert_eq!(read, expected);
+
+        vars.insert("APP_BYTES_FORMAT", "base64");
+        vars.insert("APP_HEX_EIP55", "false");
+        vars.insert("APP_SER_BYTES_FORMAT", "base58");
+        vars.insert("APP_BECH32_HRP", "cosmos");
+        let config =
+            Config::from_env_with("APP", |name| vars.get(name).map(|v| v.to_string())).unwrap();
+        assert_eq!(config.ser_bytes_format(), &BytesFormat::Base58);
+        assert_eq!(config.de_bytes_format(), &BytesFormat::Base64);
+        assert_eq!(config.bech32_hrp(), "cosmos");
+    }
+
+    #[test]
+    fn test_from_str_strict_bytes() {
+        #[derive(Deserialize, Debug, PartialEq)]
+        #[serde(untagged)]
+        enum Untagged {
+            Number(u64),
+            Text(String),
+        }
+
+        #[derive(Deserialize, Debug)]
+        struct Inner {
+            name: String,
+        }
+
+        #[derive(Deserialize, Debug)]
+        struct TestStruct {
+            #[serde(with = "serde_bytes")]
+            data: Vec<u8>,
+            untagged: Untagged,
+            #[serde(flatten)]
+            inner: Inner,
+            value: serde_json::Value,
+        }
+
+        let json = r#"{"data":"cafe","untagged":"cafe","name":"abcd","value":"beef"}"#;
+
+        let config = Config::default().set_bytes_hex();
+        assert!(from_str::<TestStruct>(json, &config).is_err());
+
+        let config = Config::default().set_bytes_hex().enable_strict_bytes();
+        let result: TestStruct = from_str(json, &config).unwrap();
+        assert_eq!(result.data, vec![0xca, 0xfe]);
+        assert_eq!(result.untagged, Untagged::Text("cafe".to_string()));
+        assert_eq!(result.inner.name, "abcd");
+        assert_eq!(result.value, json!("beef"));
+
+        let result: serde_json::Value = from_str(r#"["cafe", 1]"#, &config).unwrap();
+        assert_eq!(result, json!(["cafe", 1]));
+    }
+
+    #[test]
+    fn test_from_str_bytes_in_buffered_content() {
+        use crate::BytesFormat;
+
+        #[derive(Deserialize, Debug)]
+        struct Inner {
+            #[serde(with = "crate::as_bytes")]
+            data: Vec<u8>,
+            name: String,
+        }
+
+        #[derive(Deserialize, Debug)]
+        struct Flattened {
+            #[serde(flatten)]
+            inner: Inner,
+        }
+
+        #[derive(Deserialize, Debug, PartialEq)]
+        #[serde(untagged)]
+        enum Untagged {
+            Hash { hash: Bytes<[u8; 2]> },
+            Text(String),
+        }
+
+        #[derive(Deserialize, Debug, PartialEq)]
+        #[serde(tag = "type")]
+        enum Tagged {
+            Transfer {
+                #[serde(with = "crate::as_bytes::option")]
+                memo: Option<Vec<u8>>,
+                #[serde(with = "crate::as_bytes::vec")]
+                proofs: Vec<Vec<u8>>,
+            },
+        }
+
+        let config = Config::default().set_bytes_hex().enable_strict_bytes();
+
+        let result: Flattened = from_str(r#"{"data":"cafe","name":"cafe"}"#, &config).unwrap();
+        assert_eq!(result.inner.data, vec![0xca, 0xfe]);
+        assert_eq!(result.inner.name, "cafe");
+
+        let result: Vec<Untagged> = from_str(r#"[{"hash":"0xbeef"},"abcd"]"#, &config).unwrap();
+        assert_eq!(
+            result,
+            vec![
+                Untagged::Hash {
+                    hash: Bytes([0xbe, 0xef])
+                },
+                Untagged::Text("abcd".to_string()),
+            ]
+        );
+
+        let json = r#"{"type":"Transfer","memo":"0102","proofs":["03","0405"]}"#;
+        let result: Tagged = from_str(json, &config).unwrap();
+        assert_eq!(
+            result,
+            Tagged::Transfer {
+                memo: Some(vec![0x01, 0x02]),
+                proofs: vec![vec![0x03], vec![0x04, 0x05]],
+            }
+        );
+
+        let config = Config::default().set_bytes_base64().enable_strict_bytes();
+        let result: Flattened =
+            from_value(json!({"data": "yv4=", "name": "yv4="}), &config).unwrap();
+        assert_eq!(result.inner.data, vec![0xca, 0xfe]);
+        assert_eq!(result.inner.name, "yv4=");
+
+        let config = Config::default();
+        let result: Flattened = from_str(r#"{"data":[202,254],"name":"x"}"#, &config).unwrap();
+        assert_eq!(result.inner.data, vec![0xca, 0xfe]);
+
+        // The call configuration doesn't change the scope seen by `Deserialize` impls
+        struct ScopedFormat(BytesFormat);
+
+        impl<'de> Deserialize<'de> for ScopedFormat {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                serde::de::IgnoredAny::deserialize(deserializer)?;
+                Ok(ScopedFormat(crate::current_config().bytes_format().clone()))
+            }
+        }
+
+        let scope = Config::default().set_bytes_base58();
+        let config = Config::default().set_bytes_hex();
+        let (result, scoped): (Flattened, ScopedFormat) = crate::with_config(&scope, || {
+            from_str(r#"[{"data":"cafe","name":"x"},null]"#, &config).unwrap()
+        });
+        assert_eq!(result.inner.data, vec![0xca, 0xfe]);
+        assert_eq!(scoped.0, BytesFormat::Base58);
+
+        // Path and type rules apply where the buffer read the value
+        #[derive(Deserialize, Debug)]
+        struct Signature {
+            #[serde(with = "crate::as_bytes")]
+            sig: Vec<u8>,
+        }
+
+        #[derive(Deserialize, Debug)]
+        struct Signed {
+            #[serde(with = "crate::as_bytes")]
+            data: Vec<u8>,
+            #[serde(flatten)]
+            signature: Signature,
+        }
+
+        #[derive(Deserialize, Debug)]
+        struct Envelope(Signed);
+
+        let config = Config::default()
+            .set_bytes_hex()
+            .enable_strict_bytes()
+            .add_path_rule(
+                "$.sig",
+                Config::default().set_bytes_base64().enable_strict_bytes(),
+            );
+        let result: Signed = from_str(r#"{"data":"cafe","sig":"yv4="}"#, &config).unwrap();
+        assert_eq!(result.data, vec![0xca, 0xfe]);
+        assert_eq!(result.signature.sig, vec![0xca, 0xfe]);
+
+        let config = Config::default()
+            .set_bytes_hex()
+            .enable_strict_bytes()
+            .add_type_rule(
+                "Envelope",
+                Config::default().set_bytes_base58().enable_strict_bytes(),
+            );
+        let result: Envelope = from_value(json!({"data": "5T", "sig": "5T"}), &config).unwrap();
+        assert_eq!(result.0.signature.sig, vec![0x01, 0x02]);
+
+        let config = Config::default()
+            .set_bytes_hex()
+            .enable_strict_bytes()
+            .add_path_rule(
+                "$.sig",
+                Config::default().set_bytes_base64().enable_strict_bytes(),
+            )
+            .add_path_rule(
+                "$.other",
+                Config::default().set_bytes_base58().enable_strict_bytes(),
+            );
+        let err = from_str::<Signed>(r#"{"data":"cafe","sig":"AQI=","other":"AQI="}"#, &config)
+            .unwrap_err();
+        assert!(
+            err.to_string()
+                .starts_with("buffered value is read at $.sig and $.other with different rules")
+        );
+    }
+
+    #[test]
+    fn test_from_str_bytes_decode_formats() {
+        use crate::BytesFormat;
+
+        #[derive(Deserialize, Debug)]
+        struct TestStruct {
+            #[serde(with = "serde_bytes")]
+            data: Vec<u8>,
+        }
+
+        let config = Config::default()
+            .set_bytes_hex()
+            .enable_hex_prefix()
+            .set_bytes_decode_formats(&[
+                BytesFormat::Hex,
+                BytesFormat::Base64,
+                BytesFormat::Base64UrlSafe,
+            ]);
+
+        for (json, expected) in [
+            (r#"{"data":[202,254]}"#, vec![0xca, 0xfe]),
+            (r#"{"data":"0xcafe"}"#, vec![0xca, 0xfe]),
+            (r#"{"data":"cafe"}"#, vec![0xca, 0xfe]),
+            (r#"{"data":"yv4="}"#, vec![0xca, 0xfe]),
+            (r#"{"data":"__4="}"#, vec![0xff, 0xfe]),
+        ] {
+            let result: TestStruct = from_str(json, &config).unwrap();
+            assert_eq!(result.data, expected);
+        }
+        assert!(from_str::<TestStruct>(r#"{"data":"!!"}"#, &config).is_err());
+        assert_eq!(
+            crate::to_string(serde_bytes::Bytes::new(&[0xca, 0xfe]), &config).unwrap(),
+            r#""0xcafe""#
+        );
+
+        let config = config.enable_bytes_ambiguity_error();
+        let err = from_str::<TestStruct>(r#"{"data":"cafe"}"#, &config).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("ambiguous bytes string, valid as Hex and Base64")
+        );
+        let result: TestStruct = from_str(r#"{"data":"0xcafe"}"#, &config).unwrap();
+        assert_eq!(result.data, vec![0xca, 0xfe]);
+        let result: TestStruct = from_str(r#"{"data":"AQI="}"#, &config).unwrap();
+        assert_eq!(result.data, vec![0x01, 0x02]);
+
+        // The bytes format is accepted when not listed
+        let config = Config::default()
+            .set_bytes_base58()
+            .set_bytes_decode_formats(&[BytesFormat::Hex]);
+        let result: TestStruct = from_str(r#"{"data":"0x0102"}"#, &config).unwrap();
+        assert_eq!(result.data, vec![0x01, 0x02]);
+        let result: TestStruct = from_str(r#"{"data":"5T"}"#, &config).unwrap();
+        assert_eq!(result.data, vec![0x01, 0x02]);
+    }
+
+    #[test]
+    fn test_from_str_separate_ser_de_formats() {
+        use crate::BytesFormat;
+
+        #[derive(Deserialize, serde::Serialize, Debug)]
+        struct TestStruct {
+            #[serde(with = "serde_bytes")]
+            data: Vec<u8>,
+        }
+
+        let config = Config::default()
+            .set_bytes_hex()
+            .set_ser_bytes_format(BytesFormat::Base64);
+        assert_eq!(config.ser_bytes_format(), &BytesFormat::Base64);
+        assert_eq!(config.de_bytes_format(), &BytesFormat::Hex);
+
+        let result: TestStruct = from_str(r#"{"data":"cafe"}"#, &config).unwrap();
+        assert_eq!(result.data, vec![0xca, 0xfe]);
+        assert_eq!(
+            crate::to_string(&result, &config).unwrap(),
+            r#"{"data":"yv4="}"#
+        );
+        assert!(from_str::<TestStruct>(r#"{"data":"yv4="}"#, &config).is_err());
+
+        // Migration: write Base64, accept hex and Base64
+        let config = Config::default()
+            .set_bytes_base64()
+            .set_de_bytes_format(BytesFormat::Hex)
+            .set_bytes_decode_formats(&[BytesFormat::Hex, BytesFormat::Base64]);
+        for json in [r#"{"data":"cafe"}"#, r#"{"data":"yv4="}"#] {
+            let result: TestStruct = from_str(json, &config).unwrap();
+            assert_eq!(result.data, vec![0xca, 0xfe]);
+        }
+        let result = TestStruct {
+            data: vec![0xca, 0xfe],
+        };
+        assert_eq!(
+            crate::to_string(&result, &config).unwrap(),
+            r#"{"data":"yv4="}"#
+        );
+        assert_eq!(config.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_from_str_length_rules() {
+        #[derive(Deserialize, Debug)]
+        struct Hash(#[serde(with = "serde_bytes")] Vec<u8>);
+
+        #[derive(Deserialize, Debug)]
+        struct Address(Hex<[u8; 4]>);
+
+        #[derive(Deserialize, Debug)]
+        struct Block {
+            hash: Hash,
+            parents: Vec<Hash>,
+            miner: Address,
+            #[serde(with = "serde_bytes")]
+            extra: Vec<u8>,
+        }
+
+        #[derive(Deserialize, Debug)]
+        struct TestStruct {
+            block: Block,
+        }
+
+        let config = Config::default()
+            .set_bytes_hex()
+            .add_length_rule("Hash", 2)
+            .add_length_rule("Address", 4);
+
+        let json =
+            r#"{"block":{"hash":"0x0102","parents":["0304"],"miner":"0x05060708","extra":"09"}}"#;
+        let result: TestStruct = from_str(json, &config).unwrap();
+        assert_eq!(result.block.hash.0, vec![0x01, 0x02]);
+        assert_eq!(result.block.parents[0].0, vec![0x03, 0x04]);
+        assert_eq!(result.block.miner.0.0, [0x05, 0x06, 0x07, 0x08]);
+        assert_eq!(result.block.extra, vec![0x09]);
+
+        let json = r#"{"block":{"hash":"0x010203","parents":[],"miner":"0x05060708","extra":""}}"#;
+        let err = from_str::<TestStruct>(json, &config).unwrap_err();
+        assert!(
+            err.to_string()
+                .starts_with("expected 2 bytes, got 3 at $.block.hash")
+        );
+
+        let json = r#"{"block":{"hash":"0x0102","parents":["0304","05"],"miner":"0x05060708","extra":""}}"#;
+        let err = from_str::<TestStruct>(json, &config).unwrap_err();
+        assert!(
+            err.to_string()
+                .starts_with("expected 2 bytes, got 1 at $.block.parents[1]")
+        );
+
+        let json = r#"{"block":{"hash":"0x0102","parents":[],"miner":"0x050607","extra":""}}"#;
+        let err = from_str::<TestStruct>(json, &config).unwrap_err();
+        assert!(
+            err.to_string()
+                .starts_with("expected 4 bytes, got 3 at $.block.miner")
+        );
+
+        let config = Config::default()
+            .set_bytes_base64()
+            .add_length_rule("Hash", 2);
+        let err = from_value::<Hash>(json!("AQID"), &config).unwrap_err();
+        assert_eq!(err.to_string(), "expected 2 bytes, got 3 at $");
+
+        let config = Config::default().add_length_rule("Hash", 2);
+        let result: Hash = from_value(json!([1, 2]), &config).unwrap();
+        assert_eq!(result.0, vec![0x01, 0x02]);
+        let err = from_str::<Hash>("[1, 2, 3]", &config).unwrap_err();
+        assert!(err.to_string().starts_with("expected 2 bytes, got 3 at $"));
+
+        #[derive(Deserialize, Debug)]
+        struct Header {
+            #[serde(with = "serde_bytes")]
+            hash: [u8; 4],
+            signatures: Vec<Base64<Vec<u8>>>,
+        }
+
+        let config = Config::default()
+            .set_bytes_hex()
+            .add_path_length_rule("$.header.hash", 4)
+            .add_path_length_rule("$.header.signatures[*]", 2);
+        assert_eq!(config.validate(), Ok(()));
+
+        let json = r#"{"header":{"hash":"01020304","signatures":["AQI="]}}"#;
+        let result: std::collections::HashMap<String, Header> = from_str(json, &config).unwrap();
+        assert_eq!(result["header"].hash, [0x01, 0x02, 0x03, 0x04]);
+        assert_eq!(result["header"].signatures[0].0, vec![0x01, 0x02]);
+
+        let json = r#"{"header":{"hash":"010203","signatures":[]}}"#;
+        let err = from_str::<std::collections::HashMap<String, Header>>(json, &config).unwrap_err();
+        assert!(
+            err.to_string()
+                .starts_with("expected 4 bytes, got 3 at $.header.hash")
+        );
+
+        let json = r#"{"header":{"hash":"01020304","signatures":["AQI=","AQ=="]}}"#;
+        let err = from_str::<std::collections::HashMap<String, Header>>(json, &config).unwrap_err();
+        assert!(
+            err.to_string()
+                .starts_with("expected 2 bytes, got 1 at $.header.signatures[1]")
+        );
+
+        let config = Config::default().add_path_length_rule("$.header..hash", 4);
+        assert!(matches!(
+            config.validate(),
+            Err(crate::ConfigError::InvalidPathPattern { .. })
+        ));
+
+        // Buffered values are checked where the buffer read them
+        #[derive(Deserialize, Debug)]
+        struct Signature {
+            #[serde(with = "crate::as_bytes")]
+            sig: Vec<u8>,
+            key: Hex<Vec<u8>>,
+        }
+
+        #[derive(Deserialize, Debug)]
+        struct Signed {
+            #[serde(flatten)]
+            signature: Signature,
+        }
+
+        for config in [
+            Config::default().set_bytes_hex(),
+            Config::default().set_bytes_hex().enable_strict_bytes(),
+        ] {
+            let config = config
+                .add_path_length_rule("$.sig", 4)
+                .add_path_length_rule("$.key", 1);
+
+            let result: Signed = from_str(r#"{"sig":"cafebabe","key":"0x01"}"#, &config).unwrap();
+            assert_eq!(result.signature.sig, vec![0xca, 0xfe, 0xba, 0xbe]);
+            assert_eq!(result.signature.key.0, vec![0x01]);
+
+            let err = from_str::<Signed>(r#"{"sig":"cafe","key":"0x01"}"#, &config).unwrap_err();
+            assert!(
+                err.to_string()
+                    .starts_with("expected 4 bytes, got 2 at $.sig")
+            );
+
+            let json = r#"{"sig":"cafebabe","key":"0x0102"}"#;
+            let err = from_str::<Signed>(json, &config).unwrap_err();
+            assert!(
+                err.to_string()
+                    .starts_with("expected 1 bytes, got 2 at $.key")
+            );
+        }
+    }
 }
@@ -0,0 +1,112 @@
+// Length checks for byte values with a length or path length rule
+
+use std::fmt;
+
+use serde::de::{self, Visitor};
+
+use crate::path::Path;
+
+/// Checks the length of decoded bytes against the length rule of the current value
+pub(crate) struct LengthVisitor<V> {
+    pub(crate) visitor: V,
+    /// Expected number of bytes, `None` when no rule applies
+    pub(crate) length: Option<usize>,
+    /// Location of the value, for the error message
+    pub(crate) path: Path,
+}
+
+/// Checks a number of decoded bytes against the length expected at `path`
+pub(crate) fn check_length<E>(length: usize, len: usize, path: &Path) -> Result<(), E>
+where
+    E: de::Error,
+{
+    if length != len {
+        return Err(E::custom(format!(
+            "expected {length} bytes, got {len} at {path}"
+        )));
+    }
+    Ok(())
+}
+
+impl<V> LengthVisitor<V> {
+    fn check<E>(&self, len: usize) -> Result<(), E>
+    where
+        E: de::Error,
+    {
+        match self.length {
+            Some(length) => check_length(length, len, &self.path),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<'de, V> Visitor<'de> for LengthVisitor<V>
+where
+    V: Visitor<'de>,
+{
+    type Value = V::Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.visitor.expecting(formatter)
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        self.visitor.visit_str(v)
+    }
+
+    fn visit_borrowed_str<E>(self, v: &'de str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        self.visitor.visit_borrowed_str(v)
+    }
+
+    fn visit_string<E>(self, v: String) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        self.visitor.visit_string(v)
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        self.check(v.len())?;
+        self.visitor.visit_bytes(v)
+    }
+
+    fn visit_borrowed_bytes<E>(self, v: &'de [u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        self.check(v.len())?;
+        self.visitor.visit_borrowed_bytes(v)
+    }
+
+    fn visit_byte_buf<E>(self, v: Vec<u8>) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        self.check(v.len())?;
+        self.visitor.visit_byte_buf(v)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::SeqAccess<'de>,
+    {
+        if self.length.is_none() {
+            return self.visitor.visit_seq(seq);
+        }
+
+        let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(byte) = seq.next_element()? {
+            bytes.push(byte);
+        }
+        self.visit_byte_buf(bytes)
+    }
+}
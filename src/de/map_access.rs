@@ -1,13 +1,17 @@
 use serde::de::{DeserializeSeed, MapAccess};
 
-use crate::{Config, de::seed::WrapSeed};
+use crate::{
+    context::Context,
+    de::{key::CaptureKey, seed::WrapSeed},
+};
 
 pub struct WrapMapAccess<'a, A> {
     pub inner: A,
-    pub config: &'a Config,
+    pub ctx: Context<'a>,
+    pub key: Option<String>,
 }
 
-impl<'de, A> MapAccess<'de> for WrapMapAccess<'de, A>
+impl<'de, 'a, A> MapAccess<'de> for WrapMapAccess<'a, A>
 where
     A: MapAccess<'de>,
 {
@@ -17,9 +21,20 @@ where
     where
         K: DeserializeSeed<'de>,
     {
+        if !self.ctx.tracks_path() {
+            return self.inner.next_key_seed(WrapSeed {
+                seed,
+                ctx: self.ctx.map_key(),
+            });
+        }
+
+        let seed = CaptureKey {
+            seed,
+            key: &mut self.key,
+        };
         self.inner.next_key_seed(WrapSeed {
             seed,
-            config: self.config,
+            ctx: self.ctx.map_key(),
         })
     }
 
@@ -27,10 +42,11 @@ where
     where
         V: DeserializeSeed<'de>,
     {
-        self.inner.next_value_seed(WrapSeed {
-            seed,
-            config: self.config,
-        })
+        let ctx = match self.key.take() {
+            Some(key) => self.ctx.key(&key),
+            None => self.ctx.clone(),
+        };
+        self.inner.next_value_seed(WrapSeed { seed, ctx })
     }
 
     fn size_hint(&self) -> Option<usize> {
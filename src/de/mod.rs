@@ -1,8 +1,12 @@
-mod bytes;
+pub(crate) mod buffered;
+pub(crate) mod bytes;
 mod deserializer;
 mod enum_access;
 pub mod from;
+mod key;
+pub(crate) mod length;
 mod map_access;
+mod number;
 mod seed;
 mod seq_access;
 // pub mod value;
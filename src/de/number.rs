@@ -0,0 +1,224 @@
+// Number deserialization utilities
+
+use std::fmt;
+
+use serde::de::Visitor;
+
+use crate::{Config, IntegerFormat, NonFiniteFloatPolicy, context::Context};
+
+/// Deserializes an integer based on the configuration
+///
+/// # Arguments
+///
+/// * `number` - Deserializes the value as a plain JSON number
+///
+/// Non-default formats go through `deserialize_any`, so integers wider than 64 bits
+/// are only supported in their string form. Object keys are always strings, so hex
+/// quantity keys also accept the decimal form `serde_json` writes.
+pub(crate) fn de_integer<'de, D, V, F>(
+    deserializer: D,
+    ctx: &Context<'_>,
+    visitor: V,
+    number: F,
+) -> Result<V::Value, D::Error>
+where
+    D: serde::de::Deserializer<'de>,
+    V: Visitor<'de>,
+    F: FnOnce(D, V) -> Result<V::Value, D::Error>,
+{
+    match ctx.config.integer_format {
+        IntegerFormat::Default => number(deserializer, visitor),
+        format => deserializer.deserialize_any(IntegerVisitor {
+            visitor,
+            format,
+            key: ctx.key,
+        }),
+    }
+}
+
+/// Accepts integers as JSON numbers or as strings in the configured format
+struct IntegerVisitor<V> {
+    visitor: V,
+    format: IntegerFormat,
+    /// The integer is an object key, decimal strings are accepted in every format
+    key: bool,
+}
+
+impl<'de, V> Visitor<'de> for IntegerVisitor<V>
+where
+    V: Visitor<'de>,
+{
+    type Value = V::Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.visitor.expecting(formatter)?;
+        match self.format {
+            IntegerFormat::Quantity => formatter.write_str(" or a hex quantity string"),
+            _ => formatter.write_str(" or a decimal string"),
+        }
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        self.visitor.visit_i64(v)
+    }
+
+    fn visit_i128<E>(self, v: i128) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        self.visitor.visit_i128(v)
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        self.visitor.visit_u64(v)
+    }
+
+    fn visit_u128<E>(self, v: u128) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        self.visitor.visit_u128(v)
+    }
+
+    fn visit_f64<E>(self, v: f64) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        self.visitor.visit_f64(v)
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        let decimal = self.format != IntegerFormat::Quantity || (self.key && !v.starts_with("0x"));
+        if decimal && v.starts_with('-') {
+            let value = decode_signed(v).map_err(E::custom)?;
+            return match i64::try_from(value) {
+                Ok(value) => self.visitor.visit_i64(value),
+                Err(_) => self.visitor.visit_i128(value),
+            };
+        }
+
+        let value = if decimal {
+            decode_unsigned(v)
+        } else {
+            decode_quantity(v)
+        }
+        .map_err(E::custom)?;
+        match u64::try_from(value) {
+            Ok(value) => self.visitor.visit_u64(value),
+            Err(_) => self.visitor.visit_u128(value),
+        }
+    }
+}
+
+/// Decodes a hex quantity string "0x1a" into an integer
+pub(crate) fn decode_quantity(value: &str) -> Result<u128, String> {
+    let digits = value
+        .strip_prefix("0x")
+        .ok_or_else(|| format!("invalid quantity {:?}: missing `0x` prefix", value))?;
+    if digits.is_empty() {
+        return Err(format!("invalid quantity {:?}: no digits", value));
+    }
+    // `from_str_radix` accepts a leading sign
+    if !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err(format!("invalid quantity {:?}: invalid hex digit", value));
+    }
+    u128::from_str_radix(digits, 16).map_err(|e| format!("invalid quantity {:?}: {}", value, e))
+}
+
+/// Decodes a decimal string "1234" into an unsigned integer
+pub(crate) fn decode_unsigned(value: &str) -> Result<u128, String> {
+    if value.is_empty() || !value.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(format!("invalid integer string {:?}: invalid digit", value));
+    }
+    value
+        .parse()
+        .map_err(|e| format!("invalid integer string {:?}: {}", value, e))
+}
+
+/// Decodes a decimal string "-1234" into a signed integer
+pub(crate) fn decode_signed(value: &str) -> Result<i128, String> {
+    value
+        .parse()
+        .map_err(|e| format!("invalid integer string {:?}: {}", value, e))
+}
+
+/// Deserializes a float based on the configured policy for non-finite floats
+///
+/// # Arguments
+///
+/// * `number` - Deserializes the value as a plain JSON number
+pub(crate) fn de_float<'de, D, V, F>(
+    deserializer: D,
+    config: &Config,
+    visitor: V,
+    number: F,
+) -> Result<V::Value, D::Error>
+where
+    D: serde::de::Deserializer<'de>,
+    V: Visitor<'de>,
+    F: FnOnce(D, V) -> Result<V::Value, D::Error>,
+{
+    match config.non_finite_float {
+        NonFiniteFloatPolicy::String => deserializer.deserialize_any(FloatVisitor { visitor }),
+        _ => number(deserializer, visitor),
+    }
+}
+
+/// Accepts floats as JSON numbers or as the strings "NaN", "Infinity" and "-Infinity"
+struct FloatVisitor<V> {
+    visitor: V,
+}
+
+impl<'de, V> Visitor<'de> for FloatVisitor<V>
+where
+    V: Visitor<'de>,
+{
+    type Value = V::Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.visitor.expecting(formatter)?;
+        formatter.write_str(r#" or one of "NaN", "Infinity", "-Infinity""#)
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        self.visitor.visit_i64(v)
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        self.visitor.visit_u64(v)
+    }
+
+    fn visit_f64<E>(self, v: f64) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        self.visitor.visit_f64(v)
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        match v {
+            "NaN" => self.visitor.visit_f64(f64::NAN),
+            "Infinity" => self.visitor.visit_f64(f64::INFINITY),
+            "-Infinity" => self.visitor.visit_f64(f64::NEG_INFINITY),
+            _ => Err(E::invalid_value(serde::de::Unexpected::Str(v), &self)),
+        }
+    }
+}
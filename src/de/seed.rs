@@ -1,13 +1,13 @@
 use serde::de;
 
-use crate::{Config, de::Deserializer};
+use crate::{context::Context, de::Deserializer};
 
 pub struct WrapSeed<'a, S> {
     pub seed: S,
-    pub config: &'a Config,
+    pub ctx: Context<'a>,
 }
 
-impl<'de, S> de::DeserializeSeed<'de> for WrapSeed<'de, S>
+impl<'de, 'a, S> de::DeserializeSeed<'de> for WrapSeed<'a, S>
 where
     S: de::DeserializeSeed<'de>,
 {
@@ -17,7 +17,7 @@ where
     where
         D2: de::Deserializer<'de>,
     {
-        let de = Deserializer::with_config(de2, self.config);
+        let de = Deserializer::with_context(de2, self.ctx);
 
         self.seed.deserialize(de)
     }
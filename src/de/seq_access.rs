@@ -1,13 +1,14 @@
 use serde::de::{DeserializeSeed, SeqAccess};
 
-use crate::{Config, de::seed::WrapSeed};
+use crate::{context::Context, de::seed::WrapSeed};
 
 pub struct WrapSeqAccess<'a, A> {
     pub inner: A,
-    pub config: &'a Config,
+    pub ctx: Context<'a>,
+    pub index: usize,
 }
 
-impl<'de, A> SeqAccess<'de> for WrapSeqAccess<'de, A>
+impl<'de, 'a, A> SeqAccess<'de> for WrapSeqAccess<'a, A>
 where
     A: SeqAccess<'de>,
 {
@@ -17,10 +18,9 @@ where
     where
         T: DeserializeSeed<'de>,
     {
-        self.inner.next_element_seed(WrapSeed {
-            seed,
-            config: self.config,
-        })
+        let ctx = self.ctx.index(self.index);
+        self.index += 1;
+        self.inner.next_element_seed(WrapSeed { seed, ctx })
     }
 
     fn size_hint(&self) -> Option<usize> {
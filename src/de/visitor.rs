@@ -2,42 +2,32 @@ use serde::de::{EnumAccess, MapAccess, SeqAccess, Visitor};
 use std::fmt;
 
 use crate::{
-    BytesFormat, Config,
+    context::Context,
     de::{
-        Deserializer, enum_access::WrapEnumAccess, map_access::WrapMapAccess,
+        Deserializer, buffered, bytes, enum_access::WrapEnumAccess, map_access::WrapMapAccess,
         seq_access::WrapSeqAccess,
     },
 };
 
-fn try_decode_bytes(config: &Config, value: &str) -> Option<Vec<u8>> {
-    match config.bytes_format {
-        BytesFormat::Default => None,
-        BytesFormat::Hex => {
-            let hex_str = if value.starts_with("0x") || value.starts_with("0X") {
-                &value[2..]
-            } else {
-                value
-            };
-            hex::decode(hex_str).ok()
-        }
-        BytesFormat::Base64 | BytesFormat::Base64UrlSafe => {
-            use base64::{Engine as _, engine::general_purpose};
-            let engine = if matches!(config.bytes_format, BytesFormat::Base64UrlSafe) {
-                &general_purpose::URL_SAFE
-            } else {
-                &general_purpose::STANDARD
-            };
-            engine.decode(value).ok()
-        }
-    }
+/// Guesses bytes from a string, unless strict bytes decoding is enabled
+///
+/// Bytes of another length than a length rule expects are left as a string, so the error
+/// is reported where bytes are requested.
+fn try_decode_bytes(ctx: &Context<'_>, value: &str) -> Option<Vec<u8>> {
+    if ctx.config.strict_bytes {
+        return None;
+    }
+    bytes::decode_str(ctx.config, value)
+        .and_then(Result::ok)
+        .filter(|bytes| ctx.length.is_none_or(|length| length == bytes.len()))
 }
 
 pub struct WrapVisitor<'a, V> {
     pub visitor: V,
-    pub config: &'a Config,
+    pub ctx: Context<'a>,
 }
 
-impl<'de, V> Visitor<'de> for WrapVisitor<'de, V>
+impl<'de, 'a, V> Visitor<'de> for WrapVisitor<'a, V>
 where
     V: Visitor<'de>,
 {
@@ -149,7 +139,8 @@ where
     where
         E: serde::de::Error,
     {
-        if let Some(bytes) = try_decode_bytes(self.config, v) {
+        buffered::record_str(&self.ctx, v);
+        if let Some(bytes) = try_decode_bytes(&self.ctx, v) {
             return self.visitor.visit_byte_buf(bytes);
         }
         self.visitor.visit_str(v)
@@ -159,7 +150,8 @@ where
     where
         E: serde::de::Error,
     {
-        if let Some(bytes) = try_decode_bytes(self.config, v) {
+        buffered::record_str(&self.ctx, v);
+        if let Some(bytes) = try_decode_bytes(&self.ctx, v) {
             return self.visitor.visit_byte_buf(bytes);
         }
         self.visitor.visit_borrowed_str(v)
@@ -169,7 +161,8 @@ where
     where
         E: serde::de::Error,
     {
-        if let Some(bytes) = try_decode_bytes(self.config, &v) {
+        buffered::record_str(&self.ctx, &v);
+        if let Some(bytes) = try_decode_bytes(&self.ctx, &v) {
             return self.visitor.visit_byte_buf(bytes);
         }
         self.visitor.visit_string(v)
@@ -207,7 +200,7 @@ where
     where
         D: serde::de::Deserializer<'de>,
     {
-        let de = Deserializer::with_config(deserializer, self.config);
+        let de = Deserializer::with_context(deserializer, self.ctx);
 
         self.visitor.visit_some(de)
     }
@@ -223,7 +216,7 @@ where
     where
         D: serde::de::Deserializer<'de>,
     {
-        let de = Deserializer::with_config(deserializer, self.config);
+        let de = Deserializer::with_context(deserializer, self.ctx);
         self.visitor.visit_newtype_struct(de)
     }
 
@@ -233,7 +226,8 @@ where
     {
         self.visitor.visit_seq(WrapSeqAccess {
             inner: seq,
-            config: self.config,
+            ctx: self.ctx,
+            index: 0,
         })
     }
 
@@ -243,7 +237,8 @@ where
     {
         self.visitor.visit_map(WrapMapAccess {
             inner: map,
-            config: self.config,
+            ctx: self.ctx,
+            key: None,
         })
     }
 
@@ -253,7 +248,7 @@ where
     {
         self.visitor.visit_enum(WrapEnumAccess {
             inner: data,
-            config: self.config,
+            ctx: self.ctx,
         })
     }
 }
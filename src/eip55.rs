@@ -0,0 +1,56 @@
+// EIP-55 mixed-case checksum encoding for hex addresses
+
+use tiny_keccak::{Hasher, Keccak};
+
+/// Length in bytes of the values EIP-55 checksums are applied to (Ethereum addresses)
+pub(crate) const ADDRESS_LEN: usize = 20;
+
+/// Applies the EIP-55 checksum to a lowercase hex string (without `0x` prefix)
+///
+/// Each letter is uppercased when the corresponding nibble of the Keccak-256
+/// hash of the lowercase hex string is 8 or greater.
+pub(crate) fn checksum(lower_hex: &str) -> String {
+    let mut hasher = Keccak::v256();
+    hasher.update(lower_hex.as_bytes());
+    let mut hash = [0u8; 32];
+    hasher.finalize(&mut hash);
+
+    lower_hex
+        .chars()
+        .enumerate()
+        .map(|(i, c)| {
+            let nibble = if i % 2 == 0 {
+                hash[i / 2] >> 4
+            } else {
+                hash[i / 2] & 0x0f
+            };
+            if c.is_ascii_alphabetic() && nibble >= 8 {
+                c.to_ascii_uppercase()
+            } else {
+                c
+            }
+        })
+        .collect()
+}
+
+/// Verifies the EIP-55 checksum of a hex string (without `0x` prefix)
+///
+/// All-lowercase and all-uppercase strings carry no checksum and are accepted,
+/// mixed-case strings must match the checksum exactly.
+pub(crate) fn verify(hex_str: &str) -> Result<(), String> {
+    let has_lower = hex_str.chars().any(|c| c.is_ascii_lowercase());
+    let has_upper = hex_str.chars().any(|c| c.is_ascii_uppercase());
+    if !(has_lower && has_upper) {
+        return Ok(());
+    }
+
+    let expected = checksum(&hex_str.to_ascii_lowercase());
+    if expected == hex_str {
+        Ok(())
+    } else {
+        Err(format!(
+            "invalid EIP-55 checksum: got 0x{}, expected 0x{}",
+            hex_str, expected
+        ))
+    }
+}
@@ -0,0 +1,177 @@
+// Process-wide and scoped default configuration
+
+//! Functions without a `config` argument, using the configuration of [`with_config`] or
+//! [`set_default_config`]
+
+use std::{
+    cell::RefCell,
+    io::Write,
+    sync::{Arc, LazyLock, RwLock},
+};
+
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use serde_json::Result;
+
+use crate::Config;
+
+static DEFAULT_CONFIG: LazyLock<RwLock<Arc<Config>>> =
+    LazyLock::new(|| RwLock::new(Arc::new(Config::default())));
+
+thread_local! {
+    static SCOPED_CONFIG: RefCell<Option<Arc<Config>>> = const { RefCell::new(None) };
+}
+
+/// Sets the process-wide default configuration used by the functions in [`global`](crate::global)
+///
+/// Typically called once at startup. Threads inside [`with_config`] keep using their
+/// scoped configuration.
+pub fn set_default_config(config: Config) {
+    let mut default = DEFAULT_CONFIG
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    *default = Arc::new(config);
+}
+
+/// Returns the configuration in effect on the current thread
+///
+/// This is the innermost [`with_config`] scope, or the process-wide default set with
+/// [`set_default_config`] (`Config::default()` if none was set).
+pub fn current_config() -> Arc<Config> {
+    SCOPED_CONFIG
+        .with(|scoped| scoped.borrow().clone())
+        .unwrap_or_else(|| {
+            DEFAULT_CONFIG
+                .read()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .clone()
+        })
+}
+
+/// Runs `f` with `config` as the configuration of the current thread
+///
+/// Scopes can be nested, the previous configuration is restored when `f` returns or
+/// panics. The scope only covers the current thread, so it does not follow futures that
+/// are polled on other threads.
+///
+/// # Example
+///
+/// ```
+/// use serde_json_ext::{Config, global, with_config};
+///
+/// let config = Config::default().set_bytes_hex().enable_hex_prefix();
+/// let json = with_config(&config, || {
+///     global::to_string(&serde_bytes::Bytes::new(&[1, 2, 3]))
+/// })
+/// .unwrap();
+/// assert_eq!(json, r#""0x010203""#);
+/// ```
+pub fn with_config<F, R>(config: &Config, f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    struct Restore(Option<Arc<Config>>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            let previous = self.0.take();
+            SCOPED_CONFIG.with(|scoped| *scoped.borrow_mut() = previous);
+        }
+    }
+
+    let previous =
+        SCOPED_CONFIG.with(|scoped| scoped.borrow_mut().replace(Arc::new(config.clone())));
+    let _restore = Restore(previous);
+
+    f()
+}
+
+/// Serializes a value to a JSON string with the current configuration
+pub fn to_string<T>(value: &T) -> Result<String>
+where
+    T: ?Sized + Serialize,
+{
+    crate::to_string(value, &current_config())
+}
+
+/// Serializes a value to a pretty-printed JSON string with the current configuration
+pub fn to_string_pretty<T>(value: &T) -> Result<String>
+where
+    T: ?Sized + Serialize,
+{
+    crate::to_string_pretty(value, &current_config())
+}
+
+/// Serializes a value to a JSON byte vector with the current configuration
+pub fn to_vec<T>(value: &T) -> Result<Vec<u8>>
+where
+    T: ?Sized + Serialize,
+{
+    crate::to_vec(value, &current_config())
+}
+
+/// Serializes a value to a pretty-printed JSON byte vector with the current configuration
+pub fn to_vec_pretty<T>(value: &T) -> Result<Vec<u8>>
+where
+    T: ?Sized + Serialize,
+{
+    crate::to_vec_pretty(value, &current_config())
+}
+
+/// Serializes a value to a JSON writer with the current configuration
+pub fn to_writer<W, T>(writer: &mut W, value: &T) -> Result<()>
+where
+    W: ?Sized + Write,
+    T: ?Sized + Serialize,
+{
+    crate::to_writer(writer, value, &current_config())
+}
+
+/// Serializes a value to a pretty-printed JSON writer with the current configuration
+pub fn to_writer_pretty<W, T>(writer: &mut W, value: &T) -> Result<()>
+where
+    W: ?Sized + Write,
+    T: ?Sized + Serialize,
+{
+    crate::to_writer_pretty(writer, value, &current_config())
+}
+
+/// Serializes a value to a `serde_json::Value` with the current configuration
+pub fn to_value<T>(value: &T) -> Result<serde_json::Value>
+where
+    T: ?Sized + Serialize,
+{
+    crate::to_value(value, &current_config())
+}
+
+/// Deserializes a value from a JSON reader with the current configuration
+pub fn from_reader<R, T>(rdr: R) -> Result<T>
+where
+    R: std::io::Read,
+    T: DeserializeOwned,
+{
+    crate::from_reader(rdr, &current_config())
+}
+
+/// Deserializes a value from a JSON byte slice with the current configuration
+pub fn from_slice<'a, T>(v: &'a [u8]) -> Result<T>
+where
+    T: Deserialize<'a>,
+{
+    crate::from_slice(v, &current_config())
+}
+
+/// Deserializes a value from a JSON string with the current configuration
+pub fn from_str<'a, T>(s: &'a str) -> Result<T>
+where
+    T: Deserialize<'a>,
+{
+    crate::from_str(s, &current_config())
+}
+
+/// Deserializes a value from a `serde_json::Value` with the current configuration
+pub fn from_value<T>(value: serde_json::Value) -> Result<T>
+where
+    T: DeserializeOwned,
+{
+    crate::from_value(value, &current_config())
+}
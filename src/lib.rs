@@ -1,8 +1,27 @@
 // Library crate for serde_json_helper
 
+mod codec;
+pub use codec::*;
+
 mod config;
 pub use config::*;
 
+pub(crate) mod context;
+pub(crate) mod eip55;
+
+pub mod global;
+pub use global::{current_config, set_default_config, with_config};
+
+mod marker;
+pub use marker::*;
+
+mod with;
+pub use with::{
+    as_base58, as_base64, as_base64_url_safe, as_bytes, as_eip55, as_hex, as_hex_prefixed,
+};
+
+pub(crate) mod path;
+
 // pub(crate) mod formatter;
 
 pub(crate) mod ser;
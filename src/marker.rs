@@ -0,0 +1,317 @@
+// Marker wrapper types selecting the bytes format of a single value
+
+use std::{fmt, sync::LazyLock};
+
+use serde::{
+    Serialize,
+    de::{self, Visitor},
+    ser,
+};
+
+use crate::{
+    Config, current_config,
+    de::{buffered, bytes, length},
+    ser::serializer::Serializer,
+    with,
+};
+
+/// Reserved newtype names of the marker types
+pub(crate) const HEX_NAME: &str = "$serde_json_ext::Hex";
+pub(crate) const BASE64_NAME: &str = "$serde_json_ext::Base64";
+pub(crate) const BASE64_URL_SAFE_NAME: &str = "$serde_json_ext::Base64UrlSafe";
+pub(crate) const BASE58_NAME: &str = "$serde_json_ext::Base58";
+pub(crate) const BYTES_NAME: &str = "$serde_json_ext::Bytes";
+
+/// Configurations of the encodings used when the marker types are serialized without
+/// `serde_json_ext`
+static HEX: LazyLock<Config> =
+    LazyLock::new(|| Config::default().set_bytes_hex().enable_hex_prefix());
+static BASE64: LazyLock<Config> = LazyLock::new(|| Config::default().set_bytes_base64());
+static BASE64_URL_SAFE: LazyLock<Config> =
+    LazyLock::new(|| Config::default().set_bytes_base64_url_safe());
+static BASE58: LazyLock<Config> = LazyLock::new(|| Config::default().set_bytes_base58());
+
+/// Returns the canonical configuration of a marker type by its reserved newtype name
+pub(crate) fn canonical_config(name: &str) -> Option<&'static Config> {
+    match name {
+        HEX_NAME => Some(&HEX),
+        BASE64_NAME => Some(&BASE64),
+        BASE64_URL_SAFE_NAME => Some(&BASE64_URL_SAFE),
+        BASE58_NAME => Some(&BASE58),
+        _ => None,
+    }
+}
+
+/// Calls `f` with the configuration a marker type is serialized with without
+/// `serde_json_ext`, [`current_config`] for `Bytes`
+fn with_canonical<F, R>(name: &str, f: F) -> R
+where
+    F: FnOnce(&Config) -> R,
+{
+    match canonical_config(name) {
+        Some(canonical) => f(canonical),
+        None => f(&current_config()),
+    }
+}
+
+/// The bytes of a marker value, serialized in its canonical encoding
+///
+/// Serializers that are not human-readable receive the raw bytes.
+struct Canonical<'a, 'c> {
+    value: &'a [u8],
+    canonical: &'c Config,
+}
+
+impl Serialize for Canonical<'_, '_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        if !serializer.is_human_readable() {
+            return serializer.serialize_bytes(self.value);
+        }
+        ser::Serializer::serialize_bytes(Serializer::new(serializer, self.canonical), self.value)
+    }
+}
+
+fn serialize_marker<S>(
+    serializer: S,
+    name: &'static str,
+    canonical: &Config,
+    value: &[u8],
+) -> Result<S::Ok, S::Error>
+where
+    S: ser::Serializer,
+{
+    serializer.serialize_newtype_struct(name, &Canonical { value, canonical })
+}
+
+fn deserialize_marker<'de, D, T>(deserializer: D, name: &'static str) -> Result<T, D::Error>
+where
+    D: de::Deserializer<'de>,
+    T: TryFrom<Vec<u8>>,
+{
+    let canonical = canonical_config(name);
+    let bytes = deserializer.deserialize_newtype_struct(name, MarkerVisitor { canonical })?;
+    with::try_from_bytes(bytes)
+}
+
+/// Accepts the canonical encoding of a marker type, an array of numbers or bytes
+struct MarkerVisitor {
+    /// Encoding of the marker type, `None` for `Bytes`
+    ///
+    /// `Bytes` values read from a buffer of this crate's deserialization functions use the
+    /// rules in effect where the buffer read them, other values [`current_config`]. Length
+    /// rules apply to the buffered strings of every marker type.
+    canonical: Option<&'static Config>,
+}
+
+impl<'de> Visitor<'de> for MarkerVisitor {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an encoded byte string or an array of bytes")
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        if !deserializer.is_human_readable() {
+            return deserializer.deserialize_byte_buf(self);
+        }
+        deserializer.deserialize_any(self)
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        let location = buffered::locate_str(v);
+        let current;
+        let config = match (self.canonical, &location) {
+            (Some(canonical), _) => canonical,
+            (None, Some(location)) => location.config().map_err(E::custom)?,
+            (None, None) => {
+                current = current_config();
+                &current
+            }
+        };
+        let bytes = bytes::decode_str(config, v)
+            .unwrap_or_else(|| Err("marker type without string encoding".to_string()))
+            .map_err(E::custom)?;
+
+        if let Some(location) = &location
+            && let Some((length, path)) = location.length().map_err(E::custom)?
+        {
+            length::check_length(length, bytes.len(), path)?;
+        }
+        Ok(bytes)
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(v.to_vec())
+    }
+
+    fn visit_byte_buf<E>(self, v: Vec<u8>) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(v)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::SeqAccess<'de>,
+    {
+        let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(byte) = seq.next_element()? {
+            bytes.push(byte);
+        }
+        Ok(bytes)
+    }
+}
+
+/// Hands decoded bytes to the visitor of a marker type as its newtype content
+pub(crate) struct NewtypeBytesVisitor<V>(pub V);
+
+impl<'de, V> Visitor<'de> for NewtypeBytesVisitor<V>
+where
+    V: Visitor<'de>,
+{
+    type Value = V::Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        self.0.expecting(formatter)
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        self.0
+            .visit_newtype_struct(de::value::BytesDeserializer::new(v))
+    }
+
+    fn visit_borrowed_bytes<E>(self, v: &'de [u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        self.0
+            .visit_newtype_struct(de::value::BorrowedBytesDeserializer::new(v))
+    }
+
+    fn visit_byte_buf<E>(self, v: Vec<u8>) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        self.visit_bytes(&v)
+    }
+
+    fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::SeqAccess<'de>,
+    {
+        self.0
+            .visit_newtype_struct(de::value::SeqAccessDeserializer::new(seq))
+    }
+}
+
+macro_rules! marker_type {
+    ($(#[$doc:meta])* $ty:ident, $name:ident) => {
+        $(#[$doc])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+        pub struct $ty<T>(pub T);
+
+        impl<T> $ty<T> {
+            /// Returns the wrapped value
+            pub fn into_inner(self) -> T {
+                self.0
+            }
+        }
+
+        impl<T> From<T> for $ty<T> {
+            fn from(value: T) -> Self {
+                $ty(value)
+            }
+        }
+
+        impl<T> Serialize for $ty<T>
+        where
+            T: AsRef<[u8]>,
+        {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: ser::Serializer,
+            {
+                with_canonical($name, |canonical| {
+                    serialize_marker(serializer, $name, canonical, self.0.as_ref())
+                })
+            }
+        }
+
+        impl<'de, T> serde::Deserialize<'de> for $ty<T>
+        where
+            T: TryFrom<Vec<u8>>,
+        {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: de::Deserializer<'de>,
+            {
+                deserialize_marker(deserializer, $name).map($ty)
+            }
+        }
+    };
+}
+
+marker_type!(
+    /// Bytes encoded as hex, whatever bytes format is configured
+    ///
+    /// With `serde_json_ext` the hex settings of the active configuration apply (prefix,
+    /// case, EIP-55). Other serializers write `0x`-prefixed lowercase hex.
+    Hex,
+    HEX_NAME
+);
+
+marker_type!(
+    /// Bytes encoded as Base64, whatever bytes format is configured
+    ///
+    /// With `serde_json_ext` the Base64 settings of the active configuration apply
+    /// (padding, decode mode). Other serializers write padded standard Base64.
+    Base64,
+    BASE64_NAME
+);
+
+marker_type!(
+    /// Bytes encoded as URL-safe Base64, whatever bytes format is configured
+    ///
+    /// With `serde_json_ext` the Base64 settings of the active configuration apply
+    /// (padding, decode mode). Other serializers write padded URL-safe Base64.
+    Base64UrlSafe,
+    BASE64_URL_SAFE_NAME
+);
+
+marker_type!(
+    /// Bytes encoded as Base58, whatever bytes format is configured
+    ///
+    /// With `serde_json_ext` the Base58 alphabet of the active configuration applies.
+    /// Other serializers use the Bitcoin alphabet.
+    Base58,
+    BASE58_NAME
+);
+
+marker_type!(
+    /// Bytes encoded in the configured bytes format
+    ///
+    /// Unlike `#[serde(with = "serde_bytes")]`, the value is also decoded reliably inside
+    /// `#[serde(flatten)]`, `#[serde(untagged)]` and internally tagged enums, where serde
+    /// buffers the input before the bytes are requested. Buffered values use the path and
+    /// type rules in effect where the buffer read them, other serializers and deserializers
+    /// the configuration of [`current_config`](crate::current_config). Combine with
+    /// [`Config::enable_strict_bytes`] to keep ordinary strings in such containers intact.
+    Bytes,
+    BYTES_NAME
+);
@@ -0,0 +1,164 @@
+// JSON path patterns and locations for path-scoped rules
+
+use std::{fmt, rc::Rc};
+
+/// A segment of a location in a JSON document
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Segment {
+    /// Object member (struct field, map key or enum variant)
+    Key(String),
+    /// Array element
+    Index(usize),
+}
+
+/// The location of a value in a JSON document, e.g. `$.tx.inputs[0].hash`
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Path(Option<Rc<PathNode>>);
+
+#[derive(Debug)]
+struct PathNode {
+    parent: Path,
+    segment: Segment,
+    depth: usize,
+}
+
+impl Path {
+    /// Returns the location of a child value
+    pub(crate) fn push(&self, segment: Segment) -> Path {
+        Path(Some(Rc::new(PathNode {
+            parent: self.clone(),
+            segment,
+            depth: self.depth() + 1,
+        })))
+    }
+
+    /// Number of segments below the document root
+    pub(crate) fn depth(&self) -> usize {
+        self.0.as_ref().map_or(0, |node| node.depth)
+    }
+
+    /// Iterates over the segments from the leaf up to the document root
+    fn segments_rev(&self) -> impl Iterator<Item = &Segment> {
+        std::iter::successors(self.0.as_deref(), |node| node.parent.0.as_deref())
+            .map(|node| &node.segment)
+    }
+}
+
+impl fmt::Display for Path {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut segments: Vec<&Segment> = self.segments_rev().collect();
+        segments.reverse();
+
+        f.write_str("$")?;
+        for segment in segments {
+            match segment {
+                Segment::Key(key) => write!(f, ".{}", key)?,
+                Segment::Index(index) => write!(f, "[{}]", index)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A segment of a path pattern
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PatternSegment {
+    Key(String),
+    Index(usize),
+    /// `*` or `[*]`, matches any single segment
+    Wildcard,
+}
+
+/// A JSON path pattern such as `$.tx.signature`, `$.tx.*.hash` or `$.inputs[0].data`
+///
+/// Keys are separated by `.`, array indices are written as `[0]`, keys containing
+/// special characters can be quoted as `['key.with.dots']`, and `*` / `[*]` match
+/// any single key or index.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct PathPattern {
+    segments: Vec<PatternSegment>,
+}
+
+impl PathPattern {
+    /// Parses a path pattern, the leading `$` is optional
+    ///
+    /// Returns a description of the error for malformed patterns such as `$.tx..hash` or
+    /// `$.tx[0`.
+    pub(crate) fn parse(pattern: &str) -> Result<PathPattern, String> {
+        let (rest, mut separated) = match pattern.strip_prefix('$') {
+            Some(rest) => (rest, true),
+            None => (pattern, false),
+        };
+        let mut segments = Vec::new();
+        let mut chars = rest.chars().peekable();
+
+        while let Some(&c) = chars.peek() {
+            if c == '[' {
+                chars.next();
+                let mut inner = String::new();
+                let quote = chars.next_if(|&c| c == '\'' || c == '"');
+                let mut closed = false;
+                while let Some(c) = chars.next() {
+                    if quote == Some(c) && chars.peek() == Some(&']') {
+                        chars.next();
+                        closed = true;
+                        break;
+                    }
+                    if quote.is_none() && c == ']' {
+                        closed = true;
+                        break;
+                    }
+                    inner.push(c);
+                }
+                if !closed {
+                    return Err("unterminated `[`".to_string());
+                }
+                let segment = match (quote, inner.parse()) {
+                    (Some(_), _) => PatternSegment::Key(inner),
+                    (None, Ok(index)) => PatternSegment::Index(index),
+                    (None, Err(_)) if inner.is_empty() => return Err("empty `[]`".to_string()),
+                    (None, Err(_)) if inner == "*" => PatternSegment::Wildcard,
+                    (None, Err(_)) => PatternSegment::Key(inner),
+                };
+                segments.push(segment);
+            } else {
+                if c == '.' {
+                    chars.next();
+                } else if separated {
+                    return Err(format!("expected `.` or `[` before `{c}`"));
+                }
+                let mut name = String::new();
+                while let Some(c) = chars.next_if(|&c| c != '.' && c != '[') {
+                    name.push(c);
+                }
+                if name.is_empty() {
+                    return Err("empty key".to_string());
+                }
+                if name == "*" {
+                    segments.push(PatternSegment::Wildcard);
+                } else {
+                    segments.push(PatternSegment::Key(name));
+                }
+            }
+            separated = true;
+        }
+
+        Ok(PathPattern { segments })
+    }
+
+    /// Returns true when the pattern matches the location exactly
+    pub(crate) fn matches(&self, path: &Path) -> bool {
+        path.depth() == self.segments.len()
+            && self
+                .segments
+                .iter()
+                .rev()
+                .zip(path.segments_rev())
+                .all(|(pattern, segment)| match (pattern, segment) {
+                    (PatternSegment::Wildcard, _) => true,
+                    (PatternSegment::Key(a), Segment::Key(b)) => a == b,
+                    (PatternSegment::Index(a), Segment::Index(b)) => a == b,
+                    _ => false,
+                })
+    }
+}
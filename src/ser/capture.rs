@@ -0,0 +1,107 @@
+// Captures the raw bytes of marker values
+
+use serde::ser::{self, Impossible};
+
+/// A serializer that only accepts bytes and returns them unchanged
+///
+/// Marker values serialize their raw bytes to serializers that are not human-readable,
+/// so the wrapper can re-encode them without decoding their canonical encoding.
+pub(crate) struct BytesCapture;
+
+/// Returns the raw bytes of a marker value
+pub(crate) fn capture_bytes<T>(value: &T) -> Result<Vec<u8>, serde_json::Error>
+where
+    T: ?Sized + ser::Serialize,
+{
+    value.serialize(BytesCapture)
+}
+
+fn not_bytes() -> serde_json::Error {
+    ser::Error::custom("marker type must serialize as bytes")
+}
+
+macro_rules! reject {
+    ($($method:ident($($ty:ty),*) -> $ok:ty;)*) => {
+        $(
+            fn $method(self, $(_: $ty),*) -> Result<$ok, Self::Error> {
+                Err(not_bytes())
+            }
+        )*
+    };
+}
+
+impl ser::Serializer for BytesCapture {
+    type Ok = Vec<u8>;
+    type Error = serde_json::Error;
+    type SerializeSeq = Impossible<Vec<u8>, serde_json::Error>;
+    type SerializeTuple = Impossible<Vec<u8>, serde_json::Error>;
+    type SerializeTupleStruct = Impossible<Vec<u8>, serde_json::Error>;
+    type SerializeTupleVariant = Impossible<Vec<u8>, serde_json::Error>;
+    type SerializeMap = Impossible<Vec<u8>, serde_json::Error>;
+    type SerializeStruct = Impossible<Vec<u8>, serde_json::Error>;
+    type SerializeStructVariant = Impossible<Vec<u8>, serde_json::Error>;
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Self::Ok, Self::Error> {
+        Ok(v.to_vec())
+    }
+
+    reject! {
+        serialize_bool(bool) -> Vec<u8>;
+        serialize_i8(i8) -> Vec<u8>;
+        serialize_i16(i16) -> Vec<u8>;
+        serialize_i32(i32) -> Vec<u8>;
+        serialize_i64(i64) -> Vec<u8>;
+        serialize_i128(i128) -> Vec<u8>;
+        serialize_u8(u8) -> Vec<u8>;
+        serialize_u16(u16) -> Vec<u8>;
+        serialize_u32(u32) -> Vec<u8>;
+        serialize_u64(u64) -> Vec<u8>;
+        serialize_u128(u128) -> Vec<u8>;
+        serialize_f32(f32) -> Vec<u8>;
+        serialize_f64(f64) -> Vec<u8>;
+        serialize_char(char) -> Vec<u8>;
+        serialize_str(&str) -> Vec<u8>;
+        serialize_none() -> Vec<u8>;
+        serialize_unit() -> Vec<u8>;
+        serialize_unit_struct(&'static str) -> Vec<u8>;
+        serialize_unit_variant(&'static str, u32, &'static str) -> Vec<u8>;
+        serialize_seq(Option<usize>) -> Self::SerializeSeq;
+        serialize_tuple(usize) -> Self::SerializeTuple;
+        serialize_tuple_struct(&'static str, usize) -> Self::SerializeTupleStruct;
+        serialize_tuple_variant(&'static str, u32, &'static str, usize) -> Self::SerializeTupleVariant;
+        serialize_map(Option<usize>) -> Self::SerializeMap;
+        serialize_struct(&'static str, usize) -> Self::SerializeStruct;
+        serialize_struct_variant(&'static str, u32, &'static str, usize) -> Self::SerializeStructVariant;
+    }
+
+    fn serialize_some<T>(self, _: &T) -> Result<Self::Ok, Self::Error>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        Err(not_bytes())
+    }
+
+    fn serialize_newtype_struct<T>(self, _: &'static str, _: &T) -> Result<Self::Ok, Self::Error>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        Err(not_bytes())
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: &T,
+    ) -> Result<Self::Ok, Self::Error>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        Err(not_bytes())
+    }
+}
@@ -1,10 +1,16 @@
-use serde::ser::SerializeMap;
+use std::cell::Cell;
 
-use crate::{Config, ser::value::WrapValue};
+use serde::ser::{Error as _, SerializeMap};
+
+use crate::{
+    context::Context,
+    ser::{key::CaptureKey, value::WrapValue},
+};
 
 pub struct WrapSerializeMap<'a, Map> {
     pub inner: Map,
-    pub config: &'a Config,
+    pub ctx: Context<'a>,
+    pub key: Option<String>,
 }
 
 impl<'a, Map> SerializeMap for WrapSerializeMap<'a, Map>
@@ -18,20 +24,33 @@ where
         &mut self,
         key: &T,
     ) -> Result<(), Self::Error> {
-        self.inner.serialize_key(&WrapValue {
+        let value = WrapValue {
             value: key,
-            config: self.config,
-        })
+            ctx: self.ctx.clone(),
+        };
+        if !self.ctx.tracks_path() {
+            return self.inner.serialize_key(&value);
+        }
+
+        let key = Cell::new(None);
+        self.inner.serialize_key(&CaptureKey { value, key: &key })?;
+        self.key = key.into_inner();
+        Ok(())
     }
 
     fn serialize_value<T: ?Sized + serde::ser::Serialize>(
         &mut self,
         value: &T,
     ) -> Result<(), Self::Error> {
-        self.inner.serialize_value(&WrapValue {
-            value,
-            config: self.config,
-        })
+        let ctx = if self.ctx.tracks_path() {
+            let key = self.key.take().ok_or_else(|| {
+                Self::Error::custom("map key must serialize as a string, number or bool")
+            })?;
+            self.ctx.key(&key)
+        } else {
+            self.ctx.clone()
+        };
+        self.inner.serialize_value(&WrapValue { value, ctx })
     }
 
     fn end(self) -> Result<Self::Ok, Self::Error> {
@@ -1,6 +1,9 @@
+pub(crate) mod capture;
+pub(crate) mod key;
 pub mod map;
 pub mod seq;
 pub(crate) mod ser_bytes;
+pub(crate) mod ser_number;
 pub mod serializer;
 pub mod r#struct;
 pub mod struct_variant;
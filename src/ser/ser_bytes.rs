@@ -1,10 +1,19 @@
 // Bytes serialization utilities
 
-use crate::Config;
+use crate::{BytesFormat, Config, eip55};
 
 /// Serializes bytes as a hexadecimal string "0x1234..." or "1234..."
+///
+/// When EIP-55 is enabled, 20-byte values (addresses) are emitted with the
+/// mixed-case checksum, values of any other length use the configured case.
 pub(crate) fn ser_bytes_hex(config: &Config, value: &[u8]) -> String {
-    let hex_str = hex::encode(value);
+    let hex_str = if config.hex_eip55 && value.len() == eip55::ADDRESS_LEN {
+        eip55::checksum(&hex::encode(value))
+    } else if config.hex_uppercase {
+        hex::encode_upper(value)
+    } else {
+        hex::encode(value)
+    };
 
     if config.hex_prefix {
         format!("0x{}", hex_str)
@@ -13,17 +22,71 @@ pub(crate) fn ser_bytes_hex(config: &Config, value: &[u8]) -> String {
     }
 }
 
-/// Serializes bytes as a Base64 string
+/// Serializes bytes as a Base64 string, padded unless padding is disabled
+pub(crate) fn ser_bytes_base64(config: &Config, value: &[u8]) -> String {
+    use base64::{Engine as _, engine::general_purpose};
+    if config.base64_padding {
+        general_purpose::STANDARD.encode(value)
+    } else {
+        general_purpose::STANDARD_NO_PAD.encode(value)
+    }
+}
+
+/// Serializes bytes as a Base64 URL-safe string, padded unless padding is disabled
+pub(crate) fn ser_bytes_base64_url_safe(config: &Config, value: &[u8]) -> String {
+    use base64::{Engine as _, engine::general_purpose};
+    if config.base64_padding {
+        general_purpose::URL_SAFE.encode(value)
+    } else {
+        general_purpose::URL_SAFE_NO_PAD.encode(value)
+    }
+}
+
+/// Serializes bytes as a Base58 string
 ///
 /// # Arguments
 ///
-/// * `url_safe` - If true, uses URL-safe Base64 encoding, otherwise uses standard Base64
-pub(crate) fn ser_bytes_base64(value: &[u8]) -> String {
-    use base64::{Engine as _, engine::general_purpose};
-    general_purpose::STANDARD.encode(value)
+/// * `check` - If true, appends the Base58Check checksum before encoding
+pub(crate) fn ser_bytes_base58(config: &Config, value: &[u8], check: bool) -> String {
+    let encoder = bs58::encode(value).with_alphabet(config.base58_alphabet.as_bs58());
+    if check {
+        encoder.with_check().into_string()
+    } else {
+        encoder.into_string()
+    }
 }
 
-pub(crate) fn ser_bytes_base64_url_safe(value: &[u8]) -> String {
-    use base64::{Engine as _, engine::general_purpose};
-    general_purpose::URL_SAFE.encode(value)
+/// Serializes bytes as a Bech32 or Bech32m string with the configured human-readable part
+///
+/// # Arguments
+///
+/// * `modified` - If true, uses the Bech32m checksum, otherwise uses Bech32
+pub(crate) fn ser_bytes_bech32(
+    config: &Config,
+    value: &[u8],
+    modified: bool,
+) -> Result<String, String> {
+    use bech32::{Bech32, Bech32m, Hrp};
+    let hrp = Hrp::parse(&config.bech32_hrp)
+        .map_err(|e| format!("invalid bech32 human-readable part: {}", e))?;
+    let encoded = if modified {
+        bech32::encode::<Bech32m>(hrp, value)
+    } else {
+        bech32::encode::<Bech32>(hrp, value)
+    };
+    encoded.map_err(|e| format!("failed to encode bech32 string: {}", e))
+}
+
+/// Serializes bytes as a Base32 string
+///
+/// # Arguments
+///
+/// * `format` - One of the Base32 formats, selecting the alphabet
+pub(crate) fn ser_bytes_base32(config: &Config, value: &[u8], format: &BytesFormat) -> String {
+    let s = base32::encode(config.base32_alphabet(format), value);
+    if config.base32_lowercase && *format == BytesFormat::Base32Crockford {
+        s.to_ascii_lowercase()
+    } else {
+        s
+    }
 }
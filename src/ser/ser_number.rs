@@ -0,0 +1,115 @@
+// Number serialization utilities
+
+use serde::ser::Error as _;
+
+use crate::{Config, IntegerFormat, NonFiniteFloatPolicy};
+
+/// Largest integer JavaScript can represent exactly (`Number.MAX_SAFE_INTEGER`)
+pub(crate) const MAX_SAFE_INTEGER: u128 = (1 << 53) - 1;
+
+/// Serializes an unsigned integer based on the configuration
+///
+/// # Arguments
+///
+/// * `wide` - Whether the integer type is 64 bits or wider, only those are encoded as strings
+/// * `number` - Serializes the value as a plain JSON number
+pub(crate) fn ser_unsigned<S, F>(
+    serializer: S,
+    config: &Config,
+    value: u128,
+    wide: bool,
+    number: F,
+) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+    F: FnOnce(S) -> Result<S::Ok, S::Error>,
+{
+    match config.integer_format {
+        IntegerFormat::Quantity => serializer.serialize_str(&ser_quantity(value)),
+        IntegerFormat::String if wide => serializer.collect_str(&value),
+        IntegerFormat::SafeString if wide && value > MAX_SAFE_INTEGER => {
+            serializer.collect_str(&value)
+        }
+        _ => number(serializer),
+    }
+}
+
+/// Serializes a signed integer based on the configuration
+///
+/// # Arguments
+///
+/// * `wide` - Whether the integer type is 64 bits or wider, only those are encoded as strings
+/// * `number` - Serializes the value as a plain JSON number
+pub(crate) fn ser_signed<S, F>(
+    serializer: S,
+    config: &Config,
+    value: i128,
+    wide: bool,
+    number: F,
+) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+    F: FnOnce(S) -> Result<S::Ok, S::Error>,
+{
+    match config.integer_format {
+        IntegerFormat::Quantity => {
+            let value = u128::try_from(value).map_err(|_| {
+                S::Error::custom(format!(
+                    "negative value {} cannot be encoded as a quantity",
+                    value
+                ))
+            })?;
+            serializer.serialize_str(&ser_quantity(value))
+        }
+        IntegerFormat::String if wide => serializer.collect_str(&value),
+        IntegerFormat::SafeString if wide && value.unsigned_abs() > MAX_SAFE_INTEGER => {
+            serializer.collect_str(&value)
+        }
+        _ => number(serializer),
+    }
+}
+
+/// Encodes an integer as a hex quantity "0x1a" without leading zeros ("0x0" for zero)
+pub(crate) fn ser_quantity(value: u128) -> String {
+    format!("{:#x}", value)
+}
+
+/// Serializes a float based on the configured policy for non-finite floats
+///
+/// # Arguments
+///
+/// * `number` - Serializes the value as a plain JSON number
+pub(crate) fn ser_float<S, F>(
+    serializer: S,
+    config: &Config,
+    value: f64,
+    number: F,
+) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+    F: FnOnce(S) -> Result<S::Ok, S::Error>,
+{
+    if value.is_finite() {
+        return number(serializer);
+    }
+
+    match config.non_finite_float {
+        NonFiniteFloatPolicy::Null => number(serializer),
+        NonFiniteFloatPolicy::Error => Err(S::Error::custom(format!(
+            "non-finite float {} cannot be serialized",
+            ser_non_finite(value)
+        ))),
+        NonFiniteFloatPolicy::String => serializer.serialize_str(ser_non_finite(value)),
+    }
+}
+
+/// Encodes a non-finite float as "NaN", "Infinity" or "-Infinity"
+pub(crate) fn ser_non_finite(value: f64) -> &'static str {
+    if value.is_nan() {
+        "NaN"
+    } else if value.is_sign_positive() {
+        "Infinity"
+    } else {
+        "-Infinity"
+    }
+}
@@ -1,11 +1,20 @@
 // Serializer wrapper for serde_json::value::Serializer
 
+use serde::ser::Error as _;
+
 use crate::{
     BytesFormat, Config,
+    context::Context,
+    marker,
     ser::{
+        capture::capture_bytes,
         map::WrapSerializeMap,
         seq::WrapSerializeSeq,
-        ser_bytes::{ser_bytes_base64, ser_bytes_base64_url_safe, ser_bytes_hex},
+        ser_bytes::{
+            ser_bytes_base32, ser_bytes_base58, ser_bytes_base64, ser_bytes_base64_url_safe,
+            ser_bytes_bech32, ser_bytes_hex,
+        },
+        ser_number::{ser_float, ser_signed, ser_unsigned},
         r#struct::WrapSerializeStruct,
         struct_variant::WrapSerializeStructVariant,
         tuple::WrapSerializeTuple,
@@ -19,8 +28,8 @@ use crate::{
 pub struct Serializer<'a, S> {
     /// The internal serializer
     pub inner: S,
-    /// Configuration for serialization
-    pub config: &'a Config,
+    /// Configuration for serialization at the current location
+    pub ctx: Context<'a>,
 }
 
 impl<'a, S> Serializer<'a, S>
@@ -29,7 +38,66 @@ where
 {
     /// Creates a new `Serializer` with custom config
     pub fn new(inner: S, config: &'a Config) -> Self {
-        Serializer { inner, config }
+        Serializer {
+            inner,
+            ctx: Context::new(config),
+        }
+    }
+
+    /// Creates a new `Serializer` for a value below the document root
+    pub(crate) fn with_context(inner: S, ctx: Context<'a>) -> Self {
+        Serializer { inner, ctx }
+    }
+
+    /// Serializes bytes in the given format, using the other settings of the active config
+    pub(crate) fn serialize_bytes_as(
+        self,
+        format: &BytesFormat,
+        v: &[u8],
+    ) -> Result<S::Ok, S::Error> {
+        let config = self.ctx.config;
+        match format {
+            BytesFormat::Default => self.inner.serialize_bytes(v),
+            BytesFormat::Hex => {
+                let s = ser_bytes_hex(config, v);
+                self.inner.serialize_str(&s)
+            }
+            BytesFormat::Base64 => {
+                let s = ser_bytes_base64(config, v);
+                self.inner.serialize_str(&s)
+            }
+            BytesFormat::Base64UrlSafe => {
+                let s = ser_bytes_base64_url_safe(config, v);
+                self.inner.serialize_str(&s)
+            }
+            BytesFormat::Base58 => {
+                let s = ser_bytes_base58(config, v, false);
+                self.inner.serialize_str(&s)
+            }
+            BytesFormat::Base58Check => {
+                let s = ser_bytes_base58(config, v, true);
+                self.inner.serialize_str(&s)
+            }
+            BytesFormat::Bech32 => {
+                let s = ser_bytes_bech32(config, v, false).map_err(S::Error::custom)?;
+                self.inner.serialize_str(&s)
+            }
+            BytesFormat::Bech32m => {
+                let s = ser_bytes_bech32(config, v, true).map_err(S::Error::custom)?;
+                self.inner.serialize_str(&s)
+            }
+            BytesFormat::Base32
+            | BytesFormat::Base32Hex
+            | BytesFormat::Base32Crockford
+            | BytesFormat::ZBase32 => {
+                let s = ser_bytes_base32(config, v, format);
+                self.inner.serialize_str(&s)
+            }
+            BytesFormat::Custom(codec) => {
+                let s = codec.encode(v);
+                self.inner.serialize_str(&s)
+            }
+        }
     }
 }
 
@@ -52,51 +120,73 @@ where
     }
 
     fn serialize_i8(self, v: i8) -> Result<Self::Ok, Self::Error> {
-        self.inner.serialize_i8(v)
+        ser_signed(self.inner, self.ctx.config, v.into(), false, |s| {
+            s.serialize_i8(v)
+        })
     }
 
     fn serialize_i16(self, v: i16) -> Result<Self::Ok, Self::Error> {
-        self.inner.serialize_i16(v)
+        ser_signed(self.inner, self.ctx.config, v.into(), false, |s| {
+            s.serialize_i16(v)
+        })
     }
 
     fn serialize_i32(self, v: i32) -> Result<Self::Ok, Self::Error> {
-        self.inner.serialize_i32(v)
+        ser_signed(self.inner, self.ctx.config, v.into(), false, |s| {
+            s.serialize_i32(v)
+        })
     }
 
     fn serialize_i64(self, v: i64) -> Result<Self::Ok, Self::Error> {
-        self.inner.serialize_i64(v)
+        ser_signed(self.inner, self.ctx.config, v.into(), true, |s| {
+            s.serialize_i64(v)
+        })
     }
 
     fn serialize_i128(self, v: i128) -> Result<Self::Ok, Self::Error> {
-        self.inner.serialize_i128(v)
+        ser_signed(self.inner, self.ctx.config, v, true, |s| {
+            s.serialize_i128(v)
+        })
     }
 
     fn serialize_u8(self, v: u8) -> Result<Self::Ok, Self::Error> {
-        self.inner.serialize_u8(v)
+        ser_unsigned(self.inner, self.ctx.config, v.into(), false, |s| {
+            s.serialize_u8(v)
+        })
     }
 
     fn serialize_u16(self, v: u16) -> Result<Self::Ok, Self::Error> {
-        self.inner.serialize_u16(v)
+        ser_unsigned(self.inner, self.ctx.config, v.into(), false, |s| {
+            s.serialize_u16(v)
+        })
     }
 
     fn serialize_u32(self, v: u32) -> Result<Self::Ok, Self::Error> {
-        self.inner.serialize_u32(v)
+        ser_unsigned(self.inner, self.ctx.config, v.into(), false, |s| {
+            s.serialize_u32(v)
+        })
     }
 
     fn serialize_u64(self, v: u64) -> Result<Self::Ok, Self::Error> {
-        self.inner.serialize_u64(v)
+        ser_unsigned(self.inner, self.ctx.config, v.into(), true, |s| {
+            s.serialize_u64(v)
+        })
     }
 
     fn serialize_u128(self, v: u128) -> Result<Self::Ok, Self::Error> {
-        self.inner.serialize_u128(v)
+        ser_unsigned(self.inner, self.ctx.config, v, true, |s| {
+            s.serialize_u128(v)
+        })
     }
 
     fn serialize_f32(self, v: f32) -> Result<Self::Ok, Self::Error> {
-        self.inner.serialize_f32(v)
+        ser_float(self.inner, self.ctx.config, v.into(), |s| {
+            s.serialize_f32(v)
+        })
     }
 
     fn serialize_f64(self, v: f64) -> Result<Self::Ok, Self::Error> {
-        self.inner.serialize_f64(v)
+        ser_float(self.inner, self.ctx.config, v, |s| s.serialize_f64(v))
     }
 
     fn serialize_char(self, v: char) -> Result<Self::Ok, Self::Error> {
@@ -108,21 +198,8 @@ where
     }
 
     fn serialize_bytes(self, v: &[u8]) -> Result<Self::Ok, Self::Error> {
-        match self.config.bytes_format {
-            BytesFormat::Default => self.inner.serialize_bytes(v),
-            BytesFormat::Hex => {
-                let s = ser_bytes_hex(self.config, v);
-                self.inner.serialize_str(&s)
-            }
-            BytesFormat::Base64 => {
-                let s = ser_bytes_base64(v);
-                self.inner.serialize_str(&s)
-            }
-            BytesFormat::Base64UrlSafe => {
-                let s = ser_bytes_base64_url_safe(v);
-                self.inner.serialize_str(&s)
-            }
-        }
+        let config = self.ctx.config;
+        self.serialize_bytes_as(config.ser_bytes_format(), v)
     }
 
     fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
@@ -135,7 +212,7 @@ where
     {
         self.inner.serialize_some(&WrapValue {
             value,
-            config: self.config,
+            ctx: self.ctx,
         })
     }
 
@@ -165,7 +242,22 @@ where
     where
         T: ?Sized + serde::Serialize,
     {
-        self.inner.serialize_newtype_struct(name, value)
+        if let Some(canonical) = marker::canonical_config(name) {
+            let bytes = capture_bytes(value).map_err(S::Error::custom)?;
+            return self.serialize_bytes_as(&canonical.bytes_format, &bytes);
+        }
+        if name == marker::BYTES_NAME {
+            let bytes = capture_bytes(value).map_err(S::Error::custom)?;
+            return self.serialize_bytes(&bytes);
+        }
+
+        self.inner.serialize_newtype_struct(
+            name,
+            &WrapValue {
+                value,
+                ctx: self.ctx.newtype(name),
+            },
+        )
     }
 
     fn serialize_newtype_variant<T>(
@@ -178,15 +270,23 @@ where
     where
         T: ?Sized + serde::Serialize,
     {
-        self.inner
-            .serialize_newtype_variant(name, variant_index, variant, value)
+        self.inner.serialize_newtype_variant(
+            name,
+            variant_index,
+            variant,
+            &WrapValue {
+                value,
+                ctx: self.ctx.key(variant),
+            },
+        )
     }
 
     fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
         let inner = self.inner.serialize_seq(len)?;
         Ok(WrapSerializeSeq {
             inner,
-            config: self.config,
+            ctx: self.ctx,
+            index: 0,
         })
     }
 
@@ -194,7 +294,8 @@ where
         let inner = self.inner.serialize_tuple(len)?;
         Ok(WrapSerializeTuple {
             inner,
-            config: self.config,
+            ctx: self.ctx,
+            index: 0,
         })
     }
 
@@ -206,7 +307,8 @@ where
         let inner = self.inner.serialize_tuple_struct(name, len)?;
         Ok(WrapSerializeTupleStruct {
             inner,
-            config: self.config,
+            ctx: self.ctx,
+            index: 0,
         })
     }
 
@@ -222,7 +324,8 @@ where
             .serialize_tuple_variant(name, variant_index, variant, len)?;
         Ok(WrapSerializeTupleVariant {
             inner,
-            config: self.config,
+            ctx: self.ctx.key(variant),
+            index: 0,
         })
     }
 
@@ -230,7 +333,8 @@ where
         let inner = self.inner.serialize_map(len)?;
         Ok(WrapSerializeMap {
             inner,
-            config: self.config,
+            ctx: self.ctx,
+            key: None,
         })
     }
 
@@ -242,7 +346,7 @@ where
         let inner = self.inner.serialize_struct(name, len)?;
         Ok(WrapSerializeStruct {
             inner,
-            config: self.config,
+            ctx: self.ctx,
         })
     }
 
@@ -258,7 +362,7 @@ where
             .serialize_struct_variant(name, variant_index, variant, len)?;
         Ok(WrapSerializeStructVariant {
             inner,
-            config: self.config,
+            ctx: self.ctx.key(variant),
         })
     }
 
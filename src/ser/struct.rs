@@ -1,10 +1,10 @@
 use serde::ser::SerializeStruct;
 
-use crate::{Config, ser::value::WrapValue};
+use crate::{context::Context, ser::value::WrapValue};
 
 pub struct WrapSerializeStruct<'a, Struct> {
     pub inner: Struct,
-    pub config: &'a Config,
+    pub ctx: Context<'a>,
 }
 
 impl<'a, Struct> SerializeStruct for WrapSerializeStruct<'a, Struct>
@@ -23,7 +23,7 @@ where
             key,
             &WrapValue {
                 value,
-                config: self.config,
+                ctx: self.ctx.key(key),
             },
         )
     }
@@ -1,10 +1,10 @@
 use serde::ser::SerializeStructVariant;
 
-use crate::{Config, ser::value::WrapValue};
+use crate::{context::Context, ser::value::WrapValue};
 
 pub struct WrapSerializeStructVariant<'a, Struct> {
     pub inner: Struct,
-    pub config: &'a Config,
+    pub ctx: Context<'a>,
 }
 
 impl<'a, Struct> SerializeStructVariant for WrapSerializeStructVariant<'a, Struct>
@@ -23,7 +23,7 @@ where
             key,
             &WrapValue {
                 value,
-                config: self.config,
+                ctx: self.ctx.key(key),
             },
         )
     }
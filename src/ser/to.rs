@@ -156,6 +156,9 @@ mod tests {
     use serde_json::json;
 
     use super::*;
+    use crate::{
+        Base58, Base58Alphabet, Base64, Base64UrlSafe, Bytes, BytesCodec, Hex, NonFiniteFloatPolicy,
+    };
 
     #[test]
     fn test_to_string_bytes_default() {
@@ -610,4 +613,892 @@ mod tests {
         });
         assert_eq!(value, expect);
     }
+
+    #[test]
+    fn test_to_string_bytes_hex_eip55() {
+        #[derive(serde::Serialize)]
+        struct TestStruct {
+            #[serde(with = "serde_bytes")]
+            address: Vec<u8>,
+            #[serde(with = "serde_bytes")]
+            hash: Vec<u8>,
+        }
+
+        let test_data = TestStruct {
+            address: hex::decode("5aaeb6053f3e94c9b9a09f33669435e7ef1beaed").unwrap(),
+            hash: vec![0xab; 32],
+        };
+
+        let config = Config::default()
+            .set_bytes_hex()
+            .enable_hex_prefix()
+            .enable_hex_eip55();
+        let result = to_string(&test_data, &config).unwrap();
+        assert_eq!(
+            result,
+            r#"{"address":"0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed","hash":"0xabababababababababababababababababababababababababababababababab"}"#
+        );
+
+        let addresses = [
+            "fB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+            "dbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+            "D1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+        ];
+        for address in addresses {
+            let test_data = TestStruct {
+                address: hex::decode(address).unwrap(),
+                hash: vec![],
+            };
+            let result = to_string(&test_data, &config).unwrap();
+            assert_eq!(
+                result,
+                format!(r#"{{"address":"0x{}","hash":"0x"}}"#, address)
+            );
+        }
+    }
+
+    #[test]
+    fn test_to_string_bytes_base58() {
+        #[derive(serde::Serialize)]
+        struct TestStruct {
+            #[serde(with = "serde_bytes")]
+            data: Vec<u8>,
+        }
+
+        let test_data = TestStruct {
+            data: b"Hello World!".to_vec(),
+        };
+
+        let config = Config::default().set_bytes_base58();
+        let result = to_string(&test_data, &config).unwrap();
+        assert_eq!(result, r#"{"data":"2NEpo7TZRRrLZSi2U"}"#);
+
+        let config = Config::default()
+            .set_bytes_base58()
+            .set_base58_alphabet(Base58Alphabet::Ripple);
+        let result = to_string(&test_data, &config).unwrap();
+        assert_eq!(result, r#"{"data":"p4NFofTZRRiLZS5p7"}"#);
+
+        let test_data = TestStruct {
+            data: hex::decode("00010966776006953d5567439e5e39f86a0d273bee").unwrap(),
+        };
+
+        let config = Config::default().set_bytes_base58_check();
+        let result = to_string(&test_data, &config).unwrap();
+        assert_eq!(result, r#"{"data":"16UwLL9Risc3QfPqBUvKofHmBQ7wMtjvM"}"#);
+    }
+
+    #[test]
+    fn test_to_string_bytes_bech32() {
+        #[derive(serde::Serialize)]
+        struct TestStruct {
+            #[serde(with = "serde_bytes")]
+            address: Vec<u8>,
+        }
+
+        let test_data = TestStruct {
+            address: (0u8..20).collect(),
+        };
+
+        let config = Config::default().set_bytes_bech32("cosmos");
+        let result = to_string(&test_data, &config).unwrap();
+        assert_eq!(
+            result,
+            r#"{"address":"cosmos1qqqsyqcyq5rqwzqfpg9scrgwpugpzysnrk363e"}"#
+        );
+
+        let config = Config::default().set_bytes_bech32m("cosmos");
+        let result = to_string(&test_data, &config).unwrap();
+        assert_eq!(
+            result,
+            r#"{"address":"cosmos1qqqsyqcyq5rqwzqfpg9scrgwpugpzysnk2pk5m"}"#
+        );
+
+        let config = Config::default().set_bytes_bech32("");
+        assert!(to_string(&test_data, &config).is_err());
+    }
+
+    #[test]
+    fn test_to_string_bytes_base32() {
+        #[derive(serde::Serialize)]
+        struct TestStruct {
+            #[serde(with = "serde_bytes")]
+            data: Vec<u8>,
+        }
+
+        let test_data = TestStruct {
+            data: b"foobar".to_vec(),
+        };
+
+        let test_cases = vec![
+            (
+                Config::default().set_bytes_base32(),
+                r#"{"data":"MZXW6YTBOI======"}"#,
+            ),
+            (
+                Config::default()
+                    .set_bytes_base32()
+                    .disable_base32_padding(),
+                r#"{"data":"MZXW6YTBOI"}"#,
+            ),
+            (
+                Config::default()
+                    .set_bytes_base32()
+                    .enable_base32_lowercase(),
+                r#"{"data":"mzxw6ytboi======"}"#,
+            ),
+            (
+                Config::default().set_bytes_base32_hex(),
+                r#"{"data":"CPNMUOJ1E8======"}"#,
+            ),
+            (
+                Config::default().set_bytes_base32_crockford(),
+                r#"{"data":"CSQPYRK1E8"}"#,
+            ),
+            (
+                Config::default()
+                    .set_bytes_base32_crockford()
+                    .enable_base32_lowercase(),
+                r#"{"data":"csqpyrk1e8"}"#,
+            ),
+            (
+                Config::default().set_bytes_z_base32(),
+                r#"{"data":"c3zs6aubqe"}"#,
+            ),
+        ];
+
+        for (config, expected) in test_cases {
+            let result = to_string(&test_data, &config).unwrap();
+            assert_eq!(result, expected);
+        }
+    }
+
+    #[test]
+    fn test_to_string_bytes_base64_without_padding() {
+        #[derive(serde::Serialize)]
+        struct TestStruct {
+            #[serde(with = "serde_bytes")]
+            data: Vec<u8>,
+        }
+
+        let test_data = TestStruct {
+            data: vec![1u8, 2u8, 3u8, 255u8],
+        };
+
+        let config = Config::default()
+            .set_bytes_base64()
+            .disable_base64_padding();
+        let result = to_string(&test_data, &config).unwrap();
+        assert_eq!(result, r#"{"data":"AQID/w"}"#);
+
+        let config = Config::default()
+            .set_bytes_base64_url_safe()
+            .disable_base64_padding();
+        let result = to_string(&test_data, &config).unwrap();
+        assert_eq!(result, r#"{"data":"AQID_w"}"#);
+    }
+
+    #[test]
+    fn test_to_string_bytes_hex_uppercase() {
+        #[derive(serde::Serialize)]
+        struct TestStruct {
+            #[serde(with = "serde_bytes")]
+            data: Vec<u8>,
+        }
+
+        let test_data = TestStruct {
+            data: vec![0xde, 0xad, 0xbe, 0xef],
+        };
+
+        let config = Config::default()
+            .set_bytes_hex()
+            .enable_hex_prefix()
+            .enable_hex_uppercase();
+        let result = to_string(&test_data, &config).unwrap();
+        assert_eq!(result, r#"{"data":"0xDEADBEEF"}"#);
+
+        let config = config.disable_hex_prefix();
+        let result = to_string(&test_data, &config).unwrap();
+        assert_eq!(result, r#"{"data":"DEADBEEF"}"#);
+    }
+
+    #[test]
+    fn test_to_string_integer_quantity() {
+        #[derive(serde::Serialize)]
+        struct TestStruct {
+            number: u64,
+            gas: u32,
+            nonce: u8,
+            total_difficulty: u128,
+            offset: i64,
+        }
+
+        let test_data = TestStruct {
+            number: 436,
+            gas: 0,
+            nonce: 255,
+            total_difficulty: u128::MAX,
+            offset: 16,
+        };
+
+        let config = Config::default().set_integer_quantity();
+        let result = to_string(&test_data, &config).unwrap();
+        assert_eq!(
+            result,
+            r#"{"number":"0x1b4","gas":"0x0","nonce":"0xff","total_difficulty":"0xffffffffffffffffffffffffffffffff","offset":"0x10"}"#
+        );
+
+        let err = to_string(&-1i32, &config).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("negative value -1 cannot be encoded as a quantity")
+        );
+    }
+
+    #[test]
+    fn test_to_string_integer_string() {
+        #[derive(serde::Serialize)]
+        struct TestStruct {
+            id: u64,
+            small_id: u64,
+            delta: i64,
+            balance: u128,
+            count: u32,
+        }
+
+        let test_data = TestStruct {
+            id: 9007199254740993,
+            small_id: 42,
+            delta: -9007199254740993,
+            balance: u128::MAX,
+            count: 7,
+        };
+
+        let config = Config::default().set_integer_string();
+        let result = to_string(&test_data, &config).unwrap();
+        assert_eq!(
+            result,
+            r#"{"id":"9007199254740993","small_id":"42","delta":"-9007199254740993","balance":"340282366920938463463374607431768211455","count":7}"#
+        );
+
+        let config = Config::default().set_integer_safe_string();
+        let result = to_string(&test_data, &config).unwrap();
+        assert_eq!(
+            result,
+            r#"{"id":"9007199254740993","small_id":42,"delta":"-9007199254740993","balance":"340282366920938463463374607431768211455","count":7}"#
+        );
+
+        let result = to_string(&[9007199254740991u64, 9007199254740992u64], &config).unwrap();
+        assert_eq!(result, r#"[9007199254740991,"9007199254740992"]"#);
+    }
+
+    #[test]
+    fn test_to_string_non_finite_float() {
+        #[derive(serde::Serialize)]
+        struct TestStruct {
+            nan: f64,
+            inf: f32,
+            neg_inf: f64,
+            value: f64,
+        }
+
+        let test_data = TestStruct {
+            nan: f64::NAN,
+            inf: f32::INFINITY,
+            neg_inf: f64::NEG_INFINITY,
+            value: 1.5,
+        };
+
+        let config = Config::default();
+        let result = to_string(&test_data, &config).unwrap();
+        assert_eq!(
+            result,
+            r#"{"nan":null,"inf":null,"neg_inf":null,"value":1.5}"#
+        );
+
+        let config = Config::default().set_non_finite_float_policy(NonFiniteFloatPolicy::String);
+        let result = to_string(&test_data, &config).unwrap();
+        assert_eq!(
+            result,
+            r#"{"nan":"NaN","inf":"Infinity","neg_inf":"-Infinity","value":1.5}"#
+        );
+
+        let config = Config::default().set_non_finite_float_policy(NonFiniteFloatPolicy::Error);
+        let err = to_string(&test_data, &config).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("non-finite float NaN cannot be serialized")
+        );
+        assert_eq!(to_string(&1.5f64, &config).unwrap(), "1.5");
+    }
+
+    #[test]
+    fn test_to_string_path_rules() {
+        #[derive(serde::Serialize)]
+        struct Input {
+            #[serde(with = "serde_bytes")]
+            hash: Vec<u8>,
+        }
+
+        #[derive(serde::Serialize)]
+        struct Tx {
+            #[serde(with = "serde_bytes")]
+            signature: Vec<u8>,
+            #[serde(with = "serde_bytes")]
+            data: Vec<u8>,
+            inputs: Vec<Input>,
+            meta: std::collections::BTreeMap<String, Input>,
+        }
+
+        #[derive(serde::Serialize)]
+        struct TestStruct {
+            tx: Tx,
+            #[serde(with = "serde_bytes")]
+            extra: Vec<u8>,
+        }
+
+        let mut meta = std::collections::BTreeMap::new();
+        meta.insert("origin".to_string(), Input { hash: vec![0xab] });
+        let test_data = TestStruct {
+            tx: Tx {
+                signature: vec![0xff, 0xfe],
+                data: vec![0x01, 0x02],
+                inputs: vec![Input { hash: vec![0x0a] }, Input { hash: vec![0x0b] }],
+                meta,
+            },
+            extra: vec![0x03],
+        };
+
+        let config = Config::default()
+            .set_bytes_hex()
+            .add_path_rule("$.tx.signature", Config::default().set_bytes_base64())
+            .add_path_rule("$.tx.inputs[0]", Config::default())
+            .add_path_rule(
+                "$.tx.*[1].hash",
+                Config::default().set_bytes_hex().enable_hex_prefix(),
+            )
+            .add_path_rule("$.tx.meta.*", Config::default().set_bytes_base58());
+        let result = to_string(&test_data, &config).unwrap();
+        assert_eq!(
+            result,
+            r#"{"tx":{"signature":"//4=","data":"0102","inputs":[{"hash":[10]},{"hash":"0x0b"}],"meta":{"origin":{"hash":"3x"}}},"extra":"03"}"#
+        );
+
+        #[derive(serde::Serialize)]
+        enum Message {
+            Payload {
+                #[serde(with = "serde_bytes")]
+                data: Vec<u8>,
+            },
+        }
+
+        let config = Config::default()
+            .set_bytes_hex()
+            .add_path_rule("$.Payload.data", Config::default().set_bytes_base64());
+        let result = to_string(&Message::Payload { data: vec![0xff] }, &config).unwrap();
+        assert_eq!(result, r#"{"Payload":{"data":"/w=="}}"#);
+
+        let mut blocks = std::collections::BTreeMap::new();
+        blocks.insert(1u64, Input { hash: vec![0xff] });
+        let config = Config::default()
+            .set_integer_quantity()
+            .add_path_rule("$.0x1.hash", Config::default().set_bytes_base64());
+        let result = to_string(&blocks, &config).unwrap();
+        assert_eq!(result, r#"{"0x1":{"hash":"/w=="}}"#);
+
+        // Keys are serialized once, the path uses the key as written
+        struct CountedKey<'a>(&'a std::cell::Cell<usize>);
+
+        impl serde::Serialize for CountedKey<'_> {
+            fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+            where
+                S: serde::Serializer,
+            {
+                self.0.set(self.0.get() + 1);
+                serializer.serialize_bytes(&[0xab])
+            }
+        }
+
+        struct Keyed<'a>(&'a std::cell::Cell<usize>);
+
+        impl serde::Serialize for Keyed<'_> {
+            fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+            where
+                S: serde::Serializer,
+            {
+                use serde::ser::SerializeMap;
+
+                let mut map = serializer.serialize_map(Some(1))?;
+                map.serialize_entry(&CountedKey(self.0), &serde_bytes::Bytes::new(&[0xff]))?;
+                map.end()
+            }
+        }
+
+        let count = std::cell::Cell::new(0);
+        let config = Config::default()
+            .set_bytes_hex()
+            .add_path_rule("$.ab", Config::default().set_bytes_base64());
+        let result = to_string(&Keyed(&count), &config).unwrap();
+        assert_eq!(result, r#"{"ab":"/w=="}"#);
+        assert_eq!(count.get(), 1);
+
+        // The rule of a map doesn't apply to its values
+        #[derive(serde::Serialize, PartialEq, Eq, PartialOrd, Ord)]
+        enum Kind {
+            Hash,
+        }
+
+        let mut hashes = std::collections::BTreeMap::new();
+        hashes.insert(Kind::Hash, serde_bytes::ByteBuf::from(vec![0xff]));
+        let mut nested = std::collections::BTreeMap::new();
+        nested.insert("a", hashes);
+        let config = Config::default()
+            .set_bytes_hex()
+            .add_path_rule("$.*", Config::default().set_bytes_base64())
+            .add_path_rule("$.*.Hash", Config::default().set_bytes_base58());
+        let result = to_string(&nested, &config).unwrap();
+        assert_eq!(result, r#"{"a":{"Hash":"5Q"}}"#);
+    }
+
+    #[test]
+    fn test_to_string_type_rules() {
+        #[derive(serde::Serialize)]
+        struct Address(#[serde(with = "serde_bytes")] Vec<u8>);
+
+        #[derive(serde::Serialize)]
+        struct Signature(#[serde(with = "serde_bytes")] Vec<u8>);
+
+        #[derive(serde::Serialize)]
+        struct BlockNumber(u64);
+
+        #[derive(serde::Serialize)]
+        struct TestStruct {
+            from: Address,
+            signature: Signature,
+            number: BlockNumber,
+            nonce: u64,
+            #[serde(with = "serde_bytes")]
+            data: Vec<u8>,
+        }
+
+        let test_data = TestStruct {
+            from: Address(hex::decode("5aaeb6053f3e94c9b9a09f33669435e7ef1beaed").unwrap()),
+            signature: Signature(vec![0xff, 0xfe]),
+            number: BlockNumber(436),
+            nonce: 7,
+            data: vec![0x01, 0x02],
+        };
+
+        let config = Config::default()
+            .set_bytes_hex()
+            .add_type_rule(
+                "Address",
+                Config::default()
+                    .set_bytes_hex()
+                    .enable_hex_prefix()
+                    .enable_hex_eip55(),
+            )
+            .add_type_rule("Signature", Config::default().set_bytes_base64())
+            .add_type_rule("BlockNumber", Config::default().set_integer_quantity());
+        let result = to_string(&test_data, &config).unwrap();
+        assert_eq!(
+            result,
+            r#"{"from":"0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed","signature":"//4=","number":"0x1b4","nonce":7,"data":"0102"}"#
+        );
+    }
+
+    #[test]
+    fn test_to_string_custom_codec() {
+        struct ChecksumHex;
+
+        impl BytesCodec for ChecksumHex {
+            fn encode(&self, value: &[u8]) -> String {
+                let checksum = value.iter().fold(0u8, |acc, b| acc ^ b);
+                format!("{}:{:02x}", hex::encode(value), checksum)
+            }
+
+            fn decode(
+                &self,
+                _value: &str,
+            ) -> Result<Vec<u8>, Box<dyn std::error::Error + Send + Sync>> {
+                unreachable!()
+            }
+        }
+
+        #[derive(serde::Serialize)]
+        struct TestStruct {
+            #[serde(with = "serde_bytes")]
+            data: Vec<u8>,
+            items: Vec<serde_bytes::ByteBuf>,
+        }
+
+        let test_data = TestStruct {
+            data: vec![0x01, 0x02, 0x03],
+            items: vec![serde_bytes::ByteBuf::from(vec![0xff])],
+        };
+
+        let config = Config::default().set_bytes_custom(std::sync::Arc::new(ChecksumHex));
+        let result = to_string(&test_data, &config).unwrap();
+        assert_eq!(result, r#"{"data":"010203:00","items":["ff:ff"]}"#);
+    }
+
+    #[test]
+    fn test_to_string_marker_types() {
+        #[derive(serde::Serialize)]
+        struct TestStruct {
+            hash: Hex<Vec<u8>>,
+            signature: Base64<[u8; 2]>,
+            key: Base58<Vec<u8>>,
+            token: Option<Base64UrlSafe<Vec<u8>>>,
+            #[serde(with = "serde_bytes")]
+            data: Vec<u8>,
+        }
+
+        let test_data = TestStruct {
+            hash: Hex(vec![0xde, 0xad]),
+            signature: Base64([0xff, 0xfe]),
+            key: Base58(vec![0x00, 0x01]),
+            token: Some(Base64UrlSafe(vec![0xff, 0xfe])),
+            data: vec![0x01, 0x02],
+        };
+
+        let config = Config::default()
+            .set_bytes_base32()
+            .enable_hex_uppercase()
+            .disable_base64_padding();
+        let result = to_string(&test_data, &config).unwrap();
+        assert_eq!(
+            result,
+            r#"{"hash":"DEAD","signature":"//4","key":"12","token":"__4","data":"AEBA===="}"#
+        );
+
+        let result = serde_json::to_string(&test_data).unwrap();
+        assert_eq!(
+            result,
+            r#"{"hash":"0xdead","signature":"//4=","key":"12","token":"__4=","data":[1,2]}"#
+        );
+    }
+
+    #[test]
+    fn test_to_string_with_modules() {
+        #[derive(serde::Serialize)]
+        struct TestStruct {
+            #[serde(with = "crate::as_hex")]
+            hex: Vec<u8>,
+            #[serde(with = "crate::as_hex_prefixed")]
+            hash: [u8; 2],
+            #[serde(with = "crate::as_eip55")]
+            address: Vec<u8>,
+            #[serde(with = "crate::as_base64")]
+            signature: Vec<u8>,
+            #[serde(with = "crate::as_base64_url_safe::option")]
+            token: Option<Vec<u8>>,
+            #[serde(with = "crate::as_base58::option")]
+            key: Option<Vec<u8>>,
+            #[serde(with = "crate::as_hex_prefixed::vec")]
+            proofs: Vec<Vec<u8>>,
+        }
+
+        let test_data = TestStruct {
+            hex: vec![0xde, 0xad],
+            hash: [0xbe, 0xef],
+            address: hex::decode("5aaeb6053f3e94c9b9a09f33669435e7ef1beaed").unwrap(),
+            signature: vec![0xff, 0xfe],
+            token: Some(vec![0xff, 0xfe]),
+            key: None,
+            proofs: vec![vec![0x01], vec![0x02, 0x03]],
+        };
+
+        let expected = r#"{"hex":"dead","hash":"0xbeef","address":"0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed","signature":"//4=","token":"__4=","key":null,"proofs":["0x01","0x0203"]}"#;
+        assert_eq!(serde_json::to_string(&test_data).unwrap(), expected);
+
+        let config = Config::default().set_bytes_base32().enable_hex_uppercase();
+        assert_eq!(to_string(&test_data, &config).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_to_string_presets() {
+        #[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+        struct TestStruct {
+            #[serde(with = "serde_bytes")]
+            data: Vec<u8>,
+            amount: u64,
+            height: u32,
+        }
+
+        let test_data = TestStruct {
+            data: hex::decode("5aaeb6053f3e94c9b9a09f33669435e7ef1beaed").unwrap(),
+            amount: 9007199254740993,
+            height: 436,
+        };
+
+        let cases = [
+            (
+                Config::ethereum(),
+                r#"{"data":"0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed","amount":"0x20000000000001","height":"0x1b4"}"#,
+            ),
+            (
+                Config::solana(),
+                r#"{"data":"2GGwXofbFEf32nXAnqZMFw4sk8tk","amount":9007199254740993,"height":436}"#,
+            ),
+            (
+                Config::cosmos(),
+                r#"{"data":"Wq62BT8+lMm5oJ8zZpQ15+8b6u0=","amount":"9007199254740993","height":436}"#,
+            ),
+            (
+                Config::web_safe(),
+                r#"{"data":"Wq62BT8-lMm5oJ8zZpQ15-8b6u0","amount":"9007199254740993","height":436}"#,
+            ),
+        ];
+
+        for (config, expected) in cases {
+            let result = to_string(&test_data, &config).unwrap();
+            assert_eq!(result, expected);
+            let decoded: TestStruct = crate::from_str(&result, &config).unwrap();
+            assert_eq!(decoded, test_data);
+        }
+    }
+
+    #[test]
+    fn test_to_string_config() {
+        let result = serde_json::to_value(Config::ethereum()).unwrap();
+        assert_eq!(result["bytes_format"], "hex");
+        assert_eq!(result["integer_format"], "quantity");
+        assert_eq!(result["hex_prefix"], true);
+        assert_eq!(result["hex_eip55"], true);
+        assert_eq!(result["hex_prefix_policy"], "lenient");
+        assert!(result.get("path_rules").is_none());
+
+        let config: Config = serde_json::from_value(result).unwrap();
+        assert_eq!(config.integer_format(), crate::IntegerFormat::Quantity);
+
+        let config = Config::default().add_path_rule("$.sig", Config::default().set_bytes_base64());
+        let err = serde_json::to_string(&config).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "path, type and length rules can't be serialized"
+        );
+        let config = Config::default().add_length_rule("Hash", 32);
+        assert!(serde_json::to_string(&config).is_err());
+
+        struct Reversed;
+
+        impl BytesCodec for Reversed {
+            fn encode(&self, value: &[u8]) -> String {
+                hex::encode(value.iter().rev().copied().collect::<Vec<_>>())
+            }
+
+            fn decode(
+                &self,
+                value: &str,
+            ) -> std::result::Result<Vec<u8>, Box<dyn std::error::Error + Send + Sync>>
+            {
+                Ok(hex::decode(value)?.into_iter().rev().collect())
+            }
+        }
+
+        let config = Config::default().set_bytes_custom(std::sync::Arc::new(Reversed));
+        assert!(serde_json::to_string(&config).is_err());
+    }
+
+    #[test]
+    fn test_config_getters() {
+        let config = Config::ethereum()
+            .add_path_rule("$.tx.signature", Config::default().set_bytes_base64())
+            .add_type_rule("Key", Config::solana())
+            .add_length_rule("Hash", 32)
+            .add_path_length_rule("$.tx.hash", 32);
+        assert_eq!(config.bytes_format(), &crate::BytesFormat::Hex);
+        assert_eq!(config.integer_format(), crate::IntegerFormat::Quantity);
+        assert!(config.hex_prefix());
+        assert!(config.hex_eip55());
+        assert!(!config.hex_uppercase());
+        assert!(config.base64_padding());
+        assert_eq!(config.bech32_hrp(), "");
+
+        let (path, rule) = config.path_rules().next().unwrap();
+        assert_eq!(path, "$.tx.signature");
+        assert_eq!(rule.bytes_format(), &crate::BytesFormat::Base64);
+        let (name, rule) = config.type_rules().next().unwrap();
+        assert_eq!(name, "Key");
+        assert_eq!(rule.bytes_format(), &crate::BytesFormat::Base58);
+        assert_eq!(config.length_rules().collect::<Vec<_>>(), [("Hash", 32)]);
+        assert_eq!(
+            config.path_length_rules().collect::<Vec<_>>(),
+            [("$.tx.hash", 32)]
+        );
+    }
+
+    #[test]
+    fn test_config_validate() {
+        use crate::{ConfigError, HexCasePolicy, HexPrefixPolicy};
+
+        for config in [
+            Config::default(),
+            Config::ethereum(),
+            Config::solana(),
+            Config::cosmos(),
+            Config::web_safe(),
+            Config::default().set_bytes_bech32("cosmos"),
+        ] {
+            assert_eq!(config.validate(), Ok(()));
+        }
+
+        let err = Config::default()
+            .set_bytes_base64()
+            .enable_hex_eip55()
+            .validate()
+            .unwrap_err();
+        assert_eq!(
+            err,
+            ConfigError::FormatMismatch {
+                option: "hex_eip55",
+                bytes_format: crate::BytesFormat::Base64,
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "`hex_eip55` has no effect with the Base64 bytes format"
+        );
+
+        let err = Config::default()
+            .set_bytes_base58()
+            .enable_hex_prefix()
+            .validate()
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            ConfigError::FormatMismatch {
+                option: "hex_prefix",
+                ..
+            }
+        ));
+
+        let err = Config::default()
+            .set_bytes_hex()
+            .enable_hex_prefix()
+            .set_hex_prefix_policy(HexPrefixPolicy::ForbidPrefix)
+            .validate()
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "`hex_prefix` contradicts `hex_prefix_policy`"
+        );
+
+        let err = Config::ethereum()
+            .set_hex_case_policy(HexCasePolicy::Lowercase)
+            .validate()
+            .unwrap_err();
+        assert_eq!(
+            err,
+            ConfigError::Conflict {
+                option: "hex_eip55",
+                other: "hex_case_policy",
+            }
+        );
+
+        let err = Config::default()
+            .set_bytes_bech32("")
+            .validate()
+            .unwrap_err();
+        assert_eq!(err, ConfigError::InvalidBech32Hrp(String::new()));
+
+        let err = Config::default()
+            .add_type_rule(
+                "Address",
+                Config::default().set_bytes_base64().enable_hex_eip55(),
+            )
+            .validate()
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "rule `Address`: `hex_eip55` has no effect with the Base64 bytes format"
+        );
+
+        for (path, reason) in [
+            ("$.tx..hash", "empty key"),
+            ("$.tx.", "empty key"),
+            ("$.tx[0", "unterminated `[`"),
+            ("$.tx['hash]", "unterminated `[`"),
+            ("$.tx[]", "empty `[]`"),
+            ("$.tx[0]hash", "expected `.` or `[` before `h`"),
+            ("$tx", "expected `.` or `[` before `t`"),
+        ] {
+            let err = Config::default()
+                .add_path_rule(path, Config::default())
+                .validate()
+                .unwrap_err();
+            assert_eq!(
+                err,
+                ConfigError::InvalidPathPattern {
+                    path: path.to_string(),
+                    reason: reason.to_string(),
+                }
+            );
+        }
+        let err = Config::default()
+            .add_path_rule("$.tx[0", Config::default())
+            .validate()
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid path pattern `$.tx[0`: unterminated `[`"
+        );
+        for path in ["$", "tx.hash", "$.tx['a.b'][*].*", "$[0].hash"] {
+            let config = Config::default().add_path_rule(path, Config::default());
+            assert_eq!(config.validate(), Ok(()));
+        }
+    }
+
+    #[test]
+    fn test_to_string_bytes_marker() {
+        #[derive(serde::Serialize)]
+        struct Inner {
+            #[serde(with = "crate::as_bytes")]
+            data: Vec<u8>,
+            hash: Bytes<[u8; 2]>,
+        }
+
+        #[derive(serde::Serialize)]
+        #[serde(tag = "type")]
+        enum Tagged {
+            Transfer {
+                #[serde(flatten)]
+                inner: Inner,
+                #[serde(with = "crate::as_bytes::option")]
+                memo: Option<Vec<u8>>,
+                #[serde(with = "crate::as_bytes::vec")]
+                proofs: Vec<Vec<u8>>,
+            },
+        }
+
+        let test_data = Tagged::Transfer {
+            inner: Inner {
+                data: vec![0xca, 0xfe],
+                hash: Bytes([0xbe, 0xef]),
+            },
+            memo: None,
+            proofs: vec![vec![0x01]],
+        };
+
+        let config = Config::default().set_bytes_hex().enable_hex_prefix();
+        let result = to_string(&test_data, &config).unwrap();
+        assert_eq!(
+            result,
+            r#"{"type":"Transfer","data":"0xcafe","hash":"0xbeef","memo":null,"proofs":["0x01"]}"#
+        );
+
+        let result = serde_json::to_string(&test_data).unwrap();
+        assert_eq!(
+            result,
+            r#"{"type":"Transfer","data":[202,254],"hash":[190,239],"memo":null,"proofs":[[1]]}"#
+        );
+
+        let config = Config::default().set_bytes_base64();
+        let result = crate::with_config(&config, || serde_json::to_string(&test_data)).unwrap();
+        assert_eq!(
+            result,
+            r#"{"type":"Transfer","data":"yv4=","hash":"vu8=","memo":null,"proofs":["AQ=="]}"#
+        );
+    }
 }
@@ -1,10 +1,11 @@
 use serde::ser::SerializeTuple;
 
-use crate::{Config, ser::value::WrapValue};
+use crate::{context::Context, ser::value::WrapValue};
 
 pub struct WrapSerializeTuple<'a, Tup> {
     pub inner: Tup,
-    pub config: &'a Config,
+    pub ctx: Context<'a>,
+    pub index: usize,
 }
 
 impl<'a, Tup> SerializeTuple for WrapSerializeTuple<'a, Tup>
@@ -18,10 +19,9 @@ where
         &mut self,
         value: &T,
     ) -> Result<(), Self::Error> {
-        self.inner.serialize_element(&WrapValue {
-            value,
-            config: self.config,
-        })
+        let ctx = self.ctx.index(self.index);
+        self.index += 1;
+        self.inner.serialize_element(&WrapValue { value, ctx })
     }
 
     fn end(self) -> Result<Self::Ok, Self::Error> {
@@ -1,8 +1,8 @@
-use crate::{Config, ser::serializer::Serializer};
+use crate::{context::Context, ser::serializer::Serializer};
 
 pub struct WrapValue<'a, T: ?Sized> {
     pub value: &'a T,
-    pub config: &'a Config,
+    pub ctx: Context<'a>,
 }
 
 impl<'a, T: ?Sized> serde::ser::Serialize for WrapValue<'a, T>
@@ -14,6 +14,6 @@ where
         S2: serde::ser::Serializer,
     {
         self.value
-            .serialize(Serializer::new(serializer, self.config))
+            .serialize(Serializer::with_context(serializer, self.ctx.clone()))
     }
 }
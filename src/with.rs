@@ -0,0 +1,455 @@
+// Standalone `#[serde(with = "...")]` modules for byte fields
+
+use std::{fmt, marker::PhantomData};
+
+use serde::{
+    Serialize,
+    de::{self, DeserializeSeed, Visitor},
+    ser::{self, SerializeSeq},
+};
+
+use crate::{Config, de::bytes, ser::serializer::Serializer};
+
+/// Serializes bytes as a string in the format of the configuration
+pub(crate) fn serialize_bytes<S>(
+    config: &Config,
+    value: &[u8],
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: ser::Serializer,
+{
+    ser::Serializer::serialize_bytes(Serializer::new(serializer, config), value)
+}
+
+/// Deserializes bytes from a string in the format of the configuration
+pub(crate) fn deserialize_bytes<'de, D, T>(config: &Config, deserializer: D) -> Result<T, D::Error>
+where
+    D: de::Deserializer<'de>,
+    T: TryFrom<Vec<u8>>,
+{
+    let bytes = bytes::de_bytes(deserializer, config, ByteBufVisitor)?;
+    try_from_bytes(bytes)
+}
+
+/// Converts decoded bytes into the target type, e.g. a fixed-size array
+pub(crate) fn try_from_bytes<T, E>(bytes: Vec<u8>) -> Result<T, E>
+where
+    T: TryFrom<Vec<u8>>,
+    E: de::Error,
+{
+    let len = bytes.len();
+    T::try_from(bytes).map_err(|_| E::invalid_length(len, &"a byte array of matching length"))
+}
+
+struct ByteBufVisitor;
+
+impl<'de> Visitor<'de> for ByteBufVisitor {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an encoded byte string")
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(v.to_vec())
+    }
+
+    fn visit_byte_buf<E>(self, v: Vec<u8>) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(v)
+    }
+}
+
+/// A byte value serialized with a configuration
+struct Encoded<'a, 'c> {
+    config: &'c Config,
+    value: &'a [u8],
+}
+
+impl Serialize for Encoded<'_, '_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        serialize_bytes(self.config, self.value, serializer)
+    }
+}
+
+/// A byte value deserialized with a configuration
+struct Decoded<'c, T> {
+    config: &'c Config,
+    marker: PhantomData<T>,
+}
+
+impl<'de, T> DeserializeSeed<'de> for Decoded<'_, T>
+where
+    T: TryFrom<Vec<u8>>,
+{
+    type Value = T;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserialize_bytes(self.config, deserializer)
+    }
+}
+
+/// Serializes an optional byte value, `None` as `null`
+pub(crate) fn serialize_option<S, T>(
+    config: &Config,
+    value: &Option<T>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: ser::Serializer,
+    T: AsRef<[u8]>,
+{
+    match value {
+        Some(value) => serializer.serialize_some(&Encoded {
+            config,
+            value: value.as_ref(),
+        }),
+        None => serializer.serialize_none(),
+    }
+}
+
+/// Deserializes an optional byte value, `null` as `None`
+pub(crate) fn deserialize_option<'de, D, T>(
+    config: &Config,
+    deserializer: D,
+) -> Result<Option<T>, D::Error>
+where
+    D: de::Deserializer<'de>,
+    T: TryFrom<Vec<u8>>,
+{
+    struct OptionVisitor<'c, T> {
+        config: &'c Config,
+        marker: PhantomData<T>,
+    }
+
+    impl<'de, T> Visitor<'de> for OptionVisitor<'_, T>
+    where
+        T: TryFrom<Vec<u8>>,
+    {
+        type Value = Option<T>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("an encoded byte string or null")
+        }
+
+        fn visit_none<E>(self) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            Ok(None)
+        }
+
+        fn visit_unit<E>(self) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            Ok(None)
+        }
+
+        fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+        where
+            D: de::Deserializer<'de>,
+        {
+            deserialize_bytes(self.config, deserializer).map(Some)
+        }
+    }
+
+    deserializer.deserialize_option(OptionVisitor {
+        config,
+        marker: PhantomData,
+    })
+}
+
+/// Serializes a sequence of byte values as an array of strings
+pub(crate) fn serialize_vec<S, T>(
+    config: &Config,
+    value: &[T],
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: ser::Serializer,
+    T: AsRef<[u8]>,
+{
+    let mut seq = serializer.serialize_seq(Some(value.len()))?;
+    for item in value {
+        seq.serialize_element(&Encoded {
+            config,
+            value: item.as_ref(),
+        })?;
+    }
+    seq.end()
+}
+
+/// Deserializes an array of strings into a vector of byte values
+pub(crate) fn deserialize_vec<'de, D, T>(
+    config: &Config,
+    deserializer: D,
+) -> Result<Vec<T>, D::Error>
+where
+    D: de::Deserializer<'de>,
+    T: TryFrom<Vec<u8>>,
+{
+    struct VecVisitor<'c, T> {
+        config: &'c Config,
+        marker: PhantomData<T>,
+    }
+
+    impl<'de, T> Visitor<'de> for VecVisitor<'_, T>
+    where
+        T: TryFrom<Vec<u8>>,
+    {
+        type Value = Vec<T>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("an array of encoded byte strings")
+        }
+
+        fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+        where
+            A: de::SeqAccess<'de>,
+        {
+            let mut values = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+            while let Some(value) = seq.next_element_seed(Decoded {
+                config: self.config,
+                marker: PhantomData,
+            })? {
+                values.push(value);
+            }
+            Ok(values)
+        }
+    }
+
+    deserializer.deserialize_seq(VecVisitor {
+        config,
+        marker: PhantomData,
+    })
+}
+
+/// Generates a `#[serde(with = "...")]` module for the given configuration, with `option`
+/// and `vec` submodules
+macro_rules! with_module {
+    ($(#[$doc:meta])* $module:ident, $config:expr) => {
+        $(#[$doc])*
+        pub mod $module {
+            use std::sync::LazyLock;
+
+            use crate::Config;
+
+            static CONFIG: LazyLock<Config> = LazyLock::new(|| $config);
+
+            /// Serializes a byte value, e.g. `Vec<u8>` or `[u8; N]`
+            pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                T: ?Sized + AsRef<[u8]>,
+                S: serde::Serializer,
+            {
+                super::serialize_bytes(&CONFIG, value.as_ref(), serializer)
+            }
+
+            /// Deserializes a byte value, e.g. `Vec<u8>` or `[u8; N]`
+            pub fn deserialize<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+                T: TryFrom<Vec<u8>>,
+            {
+                super::deserialize_bytes(&CONFIG, deserializer)
+            }
+
+            /// For `Option<_>` byte values, `None` is written as `null`
+            pub mod option {
+                use super::CONFIG;
+
+                /// Serializes an optional byte value
+                pub fn serialize<T, S>(value: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+                where
+                    T: AsRef<[u8]>,
+                    S: serde::Serializer,
+                {
+                    super::super::serialize_option(&CONFIG, value, serializer)
+                }
+
+                /// Deserializes an optional byte value
+                pub fn deserialize<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+                where
+                    D: serde::Deserializer<'de>,
+                    T: TryFrom<Vec<u8>>,
+                {
+                    super::super::deserialize_option(&CONFIG, deserializer)
+                }
+            }
+
+            /// For `Vec<_>` of byte values, e.g. `Vec<Vec<u8>>`, written as an array of strings
+            pub mod vec {
+                use super::CONFIG;
+
+                /// Serializes a sequence of byte values
+                pub fn serialize<T, S>(value: &[T], serializer: S) -> Result<S::Ok, S::Error>
+                where
+                    T: AsRef<[u8]>,
+                    S: serde::Serializer,
+                {
+                    super::super::serialize_vec(&CONFIG, value, serializer)
+                }
+
+                /// Deserializes a vector of byte values
+                pub fn deserialize<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+                where
+                    D: serde::Deserializer<'de>,
+                    T: TryFrom<Vec<u8>>,
+                {
+                    super::super::deserialize_vec(&CONFIG, deserializer)
+                }
+            }
+        }
+    };
+}
+
+with_module!(
+    /// Bytes as lowercase hex without prefix (`"deadbeef"`)
+    ///
+    /// Works with any serializer, including plain `serde_json`. Deserialization accepts
+    /// hex with or without the `0x` prefix.
+    as_hex,
+    Config::default().set_bytes_hex()
+);
+
+with_module!(
+    /// Bytes as `0x`-prefixed lowercase hex (`"0xdeadbeef"`)
+    ///
+    /// Works with any serializer, including plain `serde_json`. Deserialization accepts
+    /// hex with or without the `0x` prefix.
+    as_hex_prefixed,
+    Config::default().set_bytes_hex().enable_hex_prefix()
+);
+
+with_module!(
+    /// Bytes as `0x`-prefixed EIP-55 checksummed hex (`"0x5aAeb6053F3E…"`)
+    ///
+    /// Works with any serializer, including plain `serde_json`. The checksum applies to
+    /// 20-byte values, other lengths are written in lowercase. Deserialization rejects
+    /// mixed-case addresses with a wrong checksum.
+    as_eip55,
+    Config::default()
+        .set_bytes_hex()
+        .enable_hex_prefix()
+        .enable_hex_eip55()
+);
+
+with_module!(
+    /// Bytes as padded standard Base64
+    ///
+    /// Works with any serializer, including plain `serde_json`.
+    as_base64,
+    Config::default().set_bytes_base64()
+);
+
+with_module!(
+    /// Bytes as padded URL-safe Base64
+    ///
+    /// Works with any serializer, including plain `serde_json`.
+    as_base64_url_safe,
+    Config::default().set_bytes_base64_url_safe()
+);
+
+with_module!(
+    /// Bytes as Base58 with the Bitcoin alphabet
+    ///
+    /// Works with any serializer, including plain `serde_json`.
+    as_base58,
+    Config::default().set_bytes_base58()
+);
+
+/// Bytes in the configured bytes format, the `#[serde(with = "...")]` counterpart of
+/// [`Bytes`](crate::Bytes)
+///
+/// Decodes reliably inside `#[serde(flatten)]`, `#[serde(untagged)]` and internally tagged
+/// enums, see [`Bytes`](crate::Bytes) for the configuration used there.
+pub mod as_bytes {
+    use serde::{Deserialize, Serialize};
+
+    use crate::Bytes;
+
+    /// Serializes a byte value, e.g. `Vec<u8>` or `[u8; N]`
+    pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: ?Sized + AsRef<[u8]>,
+        S: serde::Serializer,
+    {
+        Bytes(value.as_ref()).serialize(serializer)
+    }
+
+    /// Deserializes a byte value, e.g. `Vec<u8>` or `[u8; N]`
+    pub fn deserialize<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+        T: TryFrom<Vec<u8>>,
+    {
+        Bytes::deserialize(deserializer).map(Bytes::into_inner)
+    }
+
+    /// For `Option<_>` byte values, `None` is written as `null`
+    pub mod option {
+        use serde::{Deserialize, Serialize};
+
+        use crate::Bytes;
+
+        /// Serializes an optional byte value
+        pub fn serialize<T, S>(value: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            T: AsRef<[u8]>,
+            S: serde::Serializer,
+        {
+            value
+                .as_ref()
+                .map(|value| Bytes(value.as_ref()))
+                .serialize(serializer)
+        }
+
+        /// Deserializes an optional byte value
+        pub fn deserialize<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+        where
+            D: serde::Deserializer<'de>,
+            T: TryFrom<Vec<u8>>,
+        {
+            Option::<Bytes<T>>::deserialize(deserializer).map(|value| value.map(Bytes::into_inner))
+        }
+    }
+
+    /// For `Vec<_>` of byte values, e.g. `Vec<Vec<u8>>`, written as an array
+    pub mod vec {
+        use serde::Deserialize;
+
+        use crate::Bytes;
+
+        /// Serializes a sequence of byte values
+        pub fn serialize<T, S>(value: &[T], serializer: S) -> Result<S::Ok, S::Error>
+        where
+            T: AsRef<[u8]>,
+            S: serde::Serializer,
+        {
+            serializer.collect_seq(value.iter().map(|value| Bytes(value.as_ref())))
+        }
+
+        /// Deserializes a vector of byte values
+        pub fn deserialize<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+        where
+            D: serde::Deserializer<'de>,
+            T: TryFrom<Vec<u8>>,
+        {
+            Vec::<Bytes<T>>::deserialize(deserializer)
+                .map(|values| values.into_iter().map(Bytes::into_inner).collect())
+        }
+    }
+}
@@ -0,0 +1,80 @@
+// The process-wide default configuration, kept out of the unit tests running in parallel
+
+use std::sync::Mutex;
+
+use serde_json_ext::{Config, global, set_default_config, with_config};
+
+/// Serializes the tests changing the process-wide default
+static DEFAULT_CONFIG: Mutex<()> = Mutex::new(());
+
+#[test]
+fn test_to_string_global_config() {
+    let _lock = DEFAULT_CONFIG.lock().unwrap_or_else(|e| e.into_inner());
+    let bytes = serde_bytes::Bytes::new(&[0x01, 0x02]);
+
+    set_default_config(Config::default().set_bytes_hex().enable_hex_prefix());
+    assert_eq!(global::to_string(&bytes).unwrap(), r#""0x0102""#);
+
+    let base64 = Config::default().set_bytes_base64();
+    let hex = Config::default().set_bytes_hex();
+    with_config(&base64, || {
+        assert_eq!(global::to_string(&bytes).unwrap(), r#""AQI=""#);
+        with_config(&hex, || {
+            assert_eq!(global::to_string(&bytes).unwrap(), r#""0102""#);
+        });
+        assert_eq!(global::to_string(&bytes).unwrap(), r#""AQI=""#);
+
+        // Other threads only see the process-wide default
+        let result = std::thread::spawn(move || global::to_string(&[0x01u8]))
+            .join()
+            .unwrap()
+            .unwrap();
+        assert_eq!(result, "[1]");
+        let result = std::thread::spawn(|| global::to_string(serde_bytes::Bytes::new(&[0x01])))
+            .join()
+            .unwrap()
+            .unwrap();
+        assert_eq!(result, r#""0x01""#);
+    });
+
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        with_config(&base64, || panic!("inside scope"));
+    }));
+    assert!(result.is_err());
+    assert_eq!(global::to_string(&bytes).unwrap(), r#""0x0102""#);
+
+    set_default_config(Config::default());
+}
+
+#[test]
+fn test_bytes_marker_ignores_default_config() {
+    let _lock = DEFAULT_CONFIG.lock().unwrap_or_else(|e| e.into_inner());
+    use serde_json_ext::{Bytes, BytesFormat};
+
+    let bytes = Bytes(vec![0x71, 0xa7, 0xde]);
+    let base64 = Config::default().set_bytes_base64();
+
+    set_default_config(
+        Config::default()
+            .set_bytes_base64()
+            .set_bytes_decode_formats(&[BytesFormat::Hex, BytesFormat::Base64]),
+    );
+    assert_eq!(
+        serde_json_ext::to_string(&bytes, &base64).unwrap(),
+        r#""cafe""#
+    );
+
+    set_default_config(
+        Config::default()
+            .set_ser_bytes_format(BytesFormat::Base64)
+            .set_de_bytes_format(BytesFormat::Hex),
+    );
+    assert_eq!(
+        serde_json_ext::to_string(&bytes, &base64).unwrap(),
+        r#""cafe""#
+    );
+    let result: Bytes<Vec<u8>> = serde_json_ext::from_str(r#""cafe""#, &base64).unwrap();
+    assert_eq!(result, bytes);
+
+    set_default_config(Config::default());
+}